@@ -1,4 +1,3 @@
-#![no_coverage]
 use criterion::{black_box, criterion_group, criterion_main, Bencher, Criterion};
 use ringbuffer::{AllocRingBuffer, ConstGenericRingBuffer, RingBuffer};
 
@@ -63,6 +62,109 @@ fn benchmark_various<T: RingBuffer<i32>, F: Fn() -> T>(b: &mut Bencher, new: F)
     })
 }
 
+fn benchmark_clone(b: &mut Bencher, capacity: usize) {
+    let mut source = AllocRingBuffer::new(capacity);
+    source.extend(0..capacity as i32);
+
+    b.iter(|| {
+        let mut snapshot = source.clone();
+        for _ in 0..100 {
+            snapshot = black_box(source.clone());
+        }
+        snapshot
+    })
+}
+
+fn benchmark_clone_from(b: &mut Bencher, capacity: usize) {
+    let mut source = AllocRingBuffer::new(capacity);
+    source.extend(0..capacity as i32);
+
+    b.iter(|| {
+        let mut snapshot = source.clone();
+        for _ in 0..100 {
+            snapshot.clone_from(&source);
+            black_box(&snapshot);
+        }
+        snapshot
+    })
+}
+
+fn benchmark_clone_const(b: &mut Bencher) {
+    let mut source = ConstGenericRingBuffer::<u64, 1024>::new();
+    source.extend(0..1500);
+
+    b.iter(|| black_box(&source).clone())
+}
+
+fn benchmark_sum_iter(b: &mut Bencher, rb: &AllocRingBuffer<u8>) {
+    b.iter(|| {
+        black_box(rb)
+            .iter()
+            .fold(0u64, |acc, &i| acc.wrapping_add(u64::from(i)))
+    })
+}
+
+fn benchmark_sum_get(b: &mut Bencher, rb: &AllocRingBuffer<u8>) {
+    b.iter(|| {
+        let rb = black_box(rb);
+        (0..rb.len()).fold(0u64, |acc, i| acc.wrapping_add(u64::from(rb[i])))
+    })
+}
+
+fn benchmark_sum_iter_next(b: &mut Bencher, rb: &AllocRingBuffer<u8>) {
+    b.iter(|| {
+        let mut acc = 0u64;
+        for &i in black_box(rb).iter() {
+            acc = acc.wrapping_add(u64::from(i));
+        }
+        acc
+    })
+}
+
+fn benchmark_stride(b: &mut Bencher, rb: &AllocRingBuffer<u8>, step: usize) {
+    b.iter(|| {
+        black_box(rb)
+            .stride(step)
+            .fold(0u64, |acc, &i| acc.wrapping_add(u64::from(i)))
+    })
+}
+
+fn benchmark_stride_filter(b: &mut Bencher, rb: &AllocRingBuffer<u8>, step: usize) {
+    b.iter(|| {
+        black_box(rb)
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| i % step == 0)
+            .fold(0u64, |acc, (_, &i)| acc.wrapping_add(u64::from(i)))
+    })
+}
+
+fn benchmark_contains(b: &mut Bencher, rb: &AllocRingBuffer<u8>) {
+    b.iter(|| black_box(rb).contains(black_box(&255)))
+}
+
+fn benchmark_contains_iter_any(b: &mut Bencher, rb: &AllocRingBuffer<u8>) {
+    b.iter(|| black_box(rb).iter().any(|i| *i == black_box(255)))
+}
+
+fn benchmark_to_vec(b: &mut Bencher, rb: &AllocRingBuffer<u32>) {
+    b.iter(|| black_box(rb).to_vec())
+}
+
+fn benchmark_copy_into_vec(b: &mut Bencher, rb: &AllocRingBuffer<u32>) {
+    b.iter(|| black_box(rb).copy_into_vec())
+}
+
+fn benchmark_sum_fold_regions(b: &mut Bencher, rb: &AllocRingBuffer<u8>) {
+    b.iter(|| {
+        black_box(rb).fold_regions(0u64, |acc, region| {
+            region
+                .iter()
+                .fold(acc, |acc, &i| acc.wrapping_add(u64::from(i)))
+        })
+    })
+}
+
 macro_rules! generate_benches {
     (called, $c: tt, $rb: tt, $ty: tt, $fn: tt, $bmfunc: tt, $($i:tt),*) => {
         $(
@@ -180,6 +282,71 @@ fn criterion_benchmark(c: &mut Criterion) {
         8192,
         8195
     ];
+
+    for capacity in [16, 1024, 8192] {
+        c.bench_function(
+            &format!("AllocRingBuffer clone 100x capacity {}", capacity),
+            |b| benchmark_clone(b, capacity),
+        );
+        c.bench_function(
+            &format!("AllocRingBuffer clone_from 100x capacity {}", capacity),
+            |b| benchmark_clone_from(b, capacity),
+        );
+    }
+
+    c.bench_function(
+        "ConstGenericRingBuffer<u64, 1024> clone",
+        benchmark_clone_const,
+    );
+
+    for capacity in [1 << 10, 1 << 16] {
+        // no byte is 255, so the whole ringbuffer is searched
+        let mut bytes = AllocRingBuffer::new(capacity);
+        bytes.extend((0..capacity + capacity / 2).map(|i| (i % 255) as u8));
+        c.bench_function(
+            &format!("AllocRingBuffer<u8> contains capacity {}", capacity),
+            |b| benchmark_contains(b, &bytes),
+        );
+        c.bench_function(
+            &format!("AllocRingBuffer<u8> iter().any capacity {}", capacity),
+            |b| benchmark_contains_iter_any(b, &bytes),
+        );
+    }
+
+    // 1M integers that wrap around the middle of the allocation
+    let mut ints = AllocRingBuffer::new(1 << 20);
+    ints.extend(0..(1 << 20) + (1 << 19));
+    c.bench_function("AllocRingBuffer<u32> to_vec 1M", |b| {
+        benchmark_to_vec(b, &ints)
+    });
+    c.bench_function("AllocRingBuffer<u32> copy_into_vec 1M", |b| {
+        benchmark_copy_into_vec(b, &ints)
+    });
+
+    // 1 MiB of bytes that wrap around the middle of the allocation
+    let mut bytes = AllocRingBuffer::new(1 << 20);
+    bytes.extend((0..(1 << 20) + (1 << 19)).map(|i| i as u8));
+    c.bench_function("AllocRingBuffer<u8> index 1 MiB", |b| {
+        benchmark_sum_get(b, &bytes)
+    });
+    c.bench_function("AllocRingBuffer<u8> iter().next 1 MiB", |b| {
+        benchmark_sum_iter_next(b, &bytes)
+    });
+    c.bench_function("AllocRingBuffer<u8> iter().fold 1 MiB", |b| {
+        benchmark_sum_iter(b, &bytes)
+    });
+    c.bench_function("AllocRingBuffer<u8> fold_regions 1 MiB", |b| {
+        benchmark_sum_fold_regions(b, &bytes)
+    });
+    for step in [16, 1024] {
+        c.bench_function(&format!("AllocRingBuffer<u8> stride {} 1 MiB", step), |b| {
+            benchmark_stride(b, &bytes, step)
+        });
+        c.bench_function(
+            &format!("AllocRingBuffer<u8> enumerate().filter step {} 1 MiB", step),
+            |b| benchmark_stride_filter(b, &bytes, step),
+        );
+    }
 }
 
 criterion_group!(benches, criterion_benchmark);
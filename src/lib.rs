@@ -15,16 +15,26 @@
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
+#[cfg(feature = "std")]
+extern crate std;
 
 #[macro_use]
 pub(crate) mod ringbuffer_trait;
 
-pub use ringbuffer_trait::RingBuffer;
+pub use ringbuffer_trait::{IndexError, PushReport, PushResult, RingBuffer, RingBufferError};
 
 #[cfg(feature = "alloc")]
 mod with_alloc;
 #[cfg(feature = "alloc")]
-pub use with_alloc::alloc_ringbuffer::AllocRingBuffer;
+pub use with_alloc::alloc_ringbuffer::{
+    AllocRingBuffer, AllocRingBufferBuilder, OverwritePolicy, RollingHasher,
+};
+#[cfg(feature = "base64")]
+pub use with_alloc::base64::DecodeError;
+#[cfg(feature = "sync")]
+pub use with_alloc::broadcast::{BroadcastRingBuffer, Broadcaster, ReadError, Reader};
+#[cfg(feature = "sync")]
+pub use with_alloc::spsc::{Consumer, Producer, SpscRingBuffer};
 #[cfg(feature = "alloc")]
 pub use with_alloc::vecdeque::GrowableAllocRingBuffer;
 
@@ -39,6 +49,38 @@ const fn mask_and(cap: usize, index: usize) -> usize {
     index & (cap - 1)
 }
 
+/// Creates a full [`AllocRingBuffer`] containing the arguments, like [`vec!`](alloc::vec!).
+///
+/// `ringbuffer![a, b, c]` creates a ringbuffer with a capacity of three, holding the elements
+/// in order, so `a` is dequeued first. `ringbuffer![x; n]` creates a ringbuffer with a
+/// capacity of `n`, holding `n` clones of `x`, see [`AllocRingBuffer::filled`]. A
+/// [`ConstGenericRingBuffer`] can be created from an array literal with `From` instead.
+///
+/// ```
+/// use ringbuffer::{ringbuffer, RingBuffer};
+///
+/// let rb = ringbuffer![1, 2, 3];
+/// assert_eq!(rb.capacity(), 3);
+/// assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+///
+/// let rb = ringbuffer![0u8; 16];
+/// assert!(rb.is_full());
+/// assert_eq!(rb.to_vec(), vec![0; 16]);
+/// ```
+///
+/// A ringbuffer can't have a capacity of zero, so `ringbuffer![]` doesn't compile, and
+/// `ringbuffer![x; 0]` panics.
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! ringbuffer {
+    ($elem: expr; $n: expr) => {
+        $crate::AllocRingBuffer::filled($n, $elem)
+    };
+    ($($x: expr),+ $(,)?) => {
+        $crate::AllocRingBuffer::from([$($x),+])
+    };
+}
+
 /// Used internally. Computes the bitmask used to properly wrap the ringbuffers.
 #[inline]
 const fn mask_modulo(cap: usize, index: usize) -> usize {
@@ -51,10 +93,14 @@ mod tests {
     extern crate std;
 
     use core::fmt::Debug;
+    use core::hash::Hash;
+    use std::collections::VecDeque;
     use std::vec;
     use std::vec::Vec;
 
-    use crate::ringbuffer_trait::{RingBufferIterator, RingBufferMutIterator};
+    use crate::ringbuffer_trait::{
+        RingBufferChunks, RingBufferIterator, RingBufferMutIterator, RingBufferRChunks,
+    };
     use crate::{AllocRingBuffer, ConstGenericRingBuffer, GrowableAllocRingBuffer, RingBuffer};
 
     #[test]
@@ -110,6 +156,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn run_test_ord() {
+        use core::cmp::Ordering;
+
+        fn test_ord<RB: RingBuffer<i32> + Ord + Debug>(new: impl Fn() -> RB) {
+            let mut a = new();
+            let mut b = new();
+            assert_eq!(a.cmp(&b), Ordering::Equal);
+
+            // shorter is less on a common prefix
+            a.extend([1, 2]);
+            b.extend([1, 2, 3]);
+            assert!(a < b);
+            // but the first differing element takes precedence over the length
+            b.clear();
+            b.extend([1, 1, 5]);
+            assert!(a > b);
+
+            // a wrapped buffer compares equal to an unwrapped one with the same contents. With a
+            // capacity of 4, the elements of `wrapped` wrap around the end of the storage.
+            let mut wrapped = new();
+            wrapped.extend(0..3);
+            for _ in 0..3 {
+                let _ = wrapped.dequeue();
+            }
+            wrapped.extend(4..8);
+            let mut unwrapped = new();
+            unwrapped.extend(4..8);
+            assert_eq!(wrapped.cmp(&unwrapped), Ordering::Equal);
+            assert_eq!(wrapped.partial_cmp(&unwrapped), Some(Ordering::Equal));
+            assert_eq!(wrapped, unwrapped);
+
+            let mut sorted = [new(), wrapped, a];
+            sorted.sort();
+            assert_eq!(
+                sorted.iter().map(RingBuffer::to_vec).collect::<Vec<_>>(),
+                vec![vec![], vec![1, 2], vec![4, 5, 6, 7]]
+            );
+        }
+
+        test_ord(|| AllocRingBuffer::new(4));
+        test_ord(ConstGenericRingBuffer::<i32, 4>::new);
+        test_ord(|| GrowableAllocRingBuffer::with_capacity(4));
+
+        // alloc ringbuffers with the same elements are ordered by capacity, like PartialEq
+        let small = AllocRingBuffer::from(vec![1, 2]);
+        let mut large = AllocRingBuffer::new(4);
+        large.extend([1, 2]);
+        assert_ne!(small, large);
+        assert!(small < large);
+    }
+
     #[test]
     fn run_test_len() {
         fn test_len(mut b: impl RingBuffer<i32>) {
@@ -117,7 +215,7 @@ mod tests {
             b.push(1);
             assert_eq!(1, b.len());
             b.push(2);
-            assert_eq!(2, b.len())
+            assert_eq!(2, b.len());
         }
 
         test_len(AllocRingBuffer::new(8));
@@ -171,6 +269,61 @@ mod tests {
         test_clear(ConstGenericRingBuffer::<i32, 8>::new());
     }
 
+    #[test]
+    fn run_test_growable_zero_capacity() {
+        // unlike the fixed size ringbuffers, a growable ringbuffer may start out without space
+        let mut b = GrowableAllocRingBuffer::with_capacity(0);
+        assert!(b.is_empty());
+        b.push(1);
+        b.push(2);
+        assert_eq!(b.to_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn run_test_clear_drops_once() {
+        use std::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        fn test_clear_drops_once<'a>(mut b: impl RingBuffer<Counted<'a>>, drops: &'a Cell<usize>) {
+            // full
+            b.extend((0..4).map(|_| Counted(drops)));
+            b.clear();
+            assert_eq!(drops.get(), 4);
+
+            // wrapped, the first two are dropped when they're overwritten
+            b.extend((0..6).map(|_| Counted(drops)));
+            assert_eq!(drops.get(), 6);
+            b.clear();
+            assert_eq!(drops.get(), 10);
+
+            // the cleared slots are reused without dropping their old contents again
+            b.extend((0..3).map(|_| Counted(drops)));
+            assert_eq!(drops.get(), 10);
+            drop(b);
+            assert_eq!(drops.get(), 13);
+        }
+
+        let drops = Cell::new(0);
+        test_clear_drops_once(AllocRingBuffer::new(4), &drops);
+        let drops = Cell::new(0);
+        test_clear_drops_once(ConstGenericRingBuffer::<_, 4>::new(), &drops);
+
+        let drops = Cell::new(0);
+        let mut b = GrowableAllocRingBuffer::with_capacity(4);
+        b.extend((0..6).map(|_| Counted(&drops)));
+        b.clear();
+        assert_eq!(drops.get(), 6);
+        drop(b);
+        assert_eq!(drops.get(), 6);
+    }
+
     #[test]
     fn run_test_empty() {
         fn test_empty(mut b: impl RingBuffer<i32>) {
@@ -217,6 +370,153 @@ mod tests {
         test_iter(ConstGenericRingBuffer::<i32, 8>::new());
     }
 
+    #[test]
+    fn run_test_iter_matches_get() {
+        fn test_iter_matches_get(mut b: impl RingBuffer<i32>) {
+            // every offset of the readptr, so the elements wrap at every possible position
+            for offset in 0..8 {
+                b.clear();
+                b.extend(0..offset);
+                for _ in 0..offset {
+                    let _ = b.dequeue();
+                }
+                b.extend(0..5);
+
+                let by_index = (0..b.len()).map(|i| b.get(i).unwrap()).collect::<Vec<_>>();
+                assert_eq!(b.iter().collect::<Vec<_>>(), by_index);
+                assert!(b.iter().zip(&by_index).all(|(a, b)| core::ptr::eq(a, *b)));
+                assert_eq!(
+                    b.iter().rev().collect::<Vec<_>>(),
+                    by_index.iter().rev().copied().collect::<Vec<_>>()
+                );
+                assert_eq!(b.iter().fold(0, |acc, i| acc * 10 + i), 1234);
+                assert_eq!(b.iter().rfold(0, |acc, i| acc * 10 + i), 43210);
+
+                for start in 0..=b.len() {
+                    for end in start..=b.len() {
+                        assert_eq!(
+                            b.range(start..end).collect::<Vec<_>>(),
+                            by_index[start..end]
+                        );
+                        assert_eq!(b.range(start..end).len(), end - start);
+                    }
+                }
+            }
+        }
+
+        test_iter_matches_get(AllocRingBuffer::new(8));
+        test_iter_matches_get(AllocRingBuffer::new(5));
+        test_iter_matches_get(ConstGenericRingBuffer::<i32, 5>::new());
+        test_iter_matches_get(ConstGenericRingBuffer::<i32, 6>::new());
+        test_iter_matches_get(GrowableAllocRingBuffer::with_capacity(5));
+    }
+
+    #[test]
+    fn run_test_stride() {
+        fn test_stride(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.stride(2).next(), None);
+
+            // every offset of the readptr, so the elements wrap at every possible position
+            for offset in 0..8 {
+                b.clear();
+                b.extend(0..offset);
+                let _ = b.skip_n(offset as usize);
+                b.extend(0..7);
+
+                for step in 1..9 {
+                    assert_eq!(
+                        b.stride(step).collect::<Vec<_>>(),
+                        b.iter()
+                            .collect::<Vec<_>>()
+                            .into_iter()
+                            .step_by(step)
+                            .collect::<Vec<_>>()
+                    );
+                }
+                for n in 0..8 {
+                    assert_eq!(b.iter().nth(n), b.get(n).filter(|_| n < 7));
+                    assert_eq!(
+                        b.iter().nth_back(n),
+                        b.get(6_usize.wrapping_sub(n)).filter(|_| n < 7)
+                    );
+                }
+
+                let mut iter = b.iter();
+                assert_eq!(iter.nth(2), Some(&2));
+                assert_eq!(iter.nth_back(2), Some(&4));
+                assert_eq!(iter.collect::<Vec<_>>(), vec![&3]);
+            }
+        }
+
+        test_stride(AllocRingBuffer::new(7));
+        test_stride(AllocRingBuffer::new(8));
+        test_stride(ConstGenericRingBuffer::<i32, 7>::new());
+        test_stride(GrowableAllocRingBuffer::with_capacity(7));
+    }
+
+    #[test]
+    fn run_test_eq_sequences() {
+        fn test_eq<B>(mut b: B)
+        where
+            B: RingBuffer<i32>
+                + PartialEq<[i32]>
+                + for<'a> PartialEq<&'a [i32]>
+                + PartialEq<[i32; 3]>
+                + PartialEq<Vec<i32>>
+                + PartialEq<VecDeque<i32>>,
+        {
+            assert!(b == vec![]);
+            assert!(b == VecDeque::new());
+            assert!(b != [1, 2, 3]);
+
+            // wrap around, so the elements are no longer contiguous in the backing storage
+            b.extend(0..3);
+            let _ = b.skip_n(3);
+            b.extend([1, 2, 3]);
+
+            assert!(b == [1, 2, 3]);
+            assert!(b == *[1, 2, 3].as_slice());
+            assert!(b == [1, 2, 3].as_slice());
+            assert!(b == vec![1, 2, 3]);
+            let deque: VecDeque<i32> = (1..=3).collect();
+            assert!(b == deque);
+
+            assert!(b != [3, 2, 1]);
+            assert!(b != vec![1, 2]);
+            assert!(b != vec![1, 2, 3, 4]);
+            let deque: VecDeque<i32> = (1..=4).collect();
+            assert!(b != deque);
+        }
+
+        test_eq(AllocRingBuffer::new(4));
+        test_eq(ConstGenericRingBuffer::<i32, 4>::new());
+        test_eq(GrowableAllocRingBuffer::with_capacity(4));
+    }
+
+    #[test]
+    fn test_ringbuffer_macro() {
+        use std::string::{String, ToString};
+
+        let rb = ringbuffer![1, 2, 3];
+        assert_eq!(rb.capacity(), 3);
+        assert!(rb.is_full());
+        assert_eq!(rb, [1, 2, 3]);
+
+        let mut rb = ringbuffer!["a".to_string(), "b".to_string(),];
+        rb.push("c".to_string());
+        assert_eq!(rb, [String::from("b"), String::from("c")]);
+
+        let rb = ringbuffer![0_u8; 16];
+        assert_eq!(rb.capacity(), 16);
+        assert_eq!(rb, [0; 16]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_ringbuffer_macro_repeat_zero() {
+        let _ = ringbuffer![0; 0];
+    }
+
     #[test]
     fn run_test_forward_iter_non_power_of_two() {
         fn test_iter(mut b: impl RingBuffer<i32>) {
@@ -271,6 +571,29 @@ mod tests {
         test_iter(ConstGenericRingBuffer::<i32, 7>::new());
     }
 
+    #[test]
+    fn run_test_iter_cloned_rev() {
+        use std::string::{String, ToString};
+
+        fn test_iter_cloned_rev(mut b: impl RingBuffer<String>) {
+            assert_eq!(b.iter_cloned_rev().next(), None);
+
+            b.extend(["0", "1", "2", "3"].iter().map(|s| s.to_string()));
+            let owned: Vec<String> = b.iter_cloned_rev().collect();
+            assert_eq!(owned, vec!["3", "2", "1"]);
+
+            // the clones are independent of the elements in the buffer
+            drop(b);
+            assert_eq!(owned.len(), 3);
+        }
+
+        test_iter_cloned_rev(AllocRingBuffer::new(3));
+        test_iter_cloned_rev(ConstGenericRingBuffer::<String, 3>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.iter_cloned_rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+    }
+
     #[test]
     fn run_test_iter_ref() {
         fn test_iter<B>(mut b: B)
@@ -417,7 +740,7 @@ mod tests {
                 *el += 1;
             }
 
-            assert_eq!(vec![2, 3, 4], b.to_vec())
+            assert_eq!(vec![2, 3, 4], b.to_vec());
         }
 
         test_iter_mut(AllocRingBuffer::new(8));
@@ -425,6 +748,35 @@ mod tests {
         test_iter_mut(ConstGenericRingBuffer::<i32, 8>::new());
     }
 
+    #[test]
+    fn run_test_iter_mut_enumerated() {
+        fn test_iter_mut_enumerated(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.iter_mut_enumerated().next(), None);
+
+            // wraps around the end of the storage
+            b.extend(0..7);
+            for (i, el) in b.iter_mut_enumerated() {
+                *el *= 10_i32.pow(i as u32);
+            }
+            assert_eq!(b.to_vec(), vec![2, 30, 400, 5000, 60000]);
+
+            for (i, el) in b.iter_mut_enumerated().rev() {
+                assert_eq!(*el, 10_i32.pow(i as u32) * (i as i32 + 2));
+                *el = i as i32;
+            }
+            for i in 0..b.len() {
+                assert_eq!(b.get_mut(i), Some(&mut (i as i32)));
+            }
+        }
+
+        test_iter_mut_enumerated(AllocRingBuffer::new(5));
+        test_iter_mut_enumerated(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 1, 1]);
+        b.iter_mut_enumerated().for_each(|(i, el)| *el += i as i32);
+        assert_eq!(b.to_vec(), vec![1, 2, 3]);
+    }
+
     #[test]
     fn run_test_iter_mut_ref() {
         fn test_iter_mut<B>(mut b: B)
@@ -441,7 +793,7 @@ mod tests {
                 *el += 1;
             }
 
-            assert_eq!(vec![2, 3, 4], b.to_vec())
+            assert_eq!(vec![2, 3, 4], b.to_vec());
         }
 
         test_iter_mut(AllocRingBuffer::new(8));
@@ -460,7 +812,7 @@ mod tests {
                 *i += 1;
             }
 
-            assert_eq!(vec![3, 4], b.to_vec())
+            assert_eq!(vec![3, 4], b.to_vec());
         }
 
         run_test_iter_mut_wrap(AllocRingBuffer::new(2));
@@ -477,7 +829,7 @@ mod tests {
             *i += 1;
         }
 
-        assert_eq!(vec![2, 3, 4], b.to_vec())
+        assert_eq!(vec![2, 3, 4], b.to_vec());
     }
 
     #[test]
@@ -493,7 +845,7 @@ mod tests {
                 *i += 1;
             }
 
-            assert_eq!(vec![3, 4], b.to_vec())
+            assert_eq!(vec![3, 4], b.to_vec());
         }
 
         run_test_iter_mut_wrap(AllocRingBuffer::new(2));
@@ -511,7 +863,7 @@ mod tests {
             *i += 1;
         }
 
-        assert_eq!(vec![2, 3, 4], b.to_vec())
+        assert_eq!(vec![2, 3, 4], b.to_vec());
     }
 
     #[test]
@@ -521,7 +873,7 @@ mod tests {
             b.push(2);
             b.push(3);
 
-            assert_eq!(vec![1, 2, 3], b.to_vec())
+            assert_eq!(vec![1, 2, 3], b.to_vec());
         }
 
         test_to_vec(AllocRingBuffer::new(8));
@@ -537,7 +889,7 @@ mod tests {
             // Wrap
             b.push(3);
 
-            assert_eq!(vec![2, 3], b.to_vec())
+            assert_eq!(vec![2, 3], b.to_vec());
         }
 
         test_to_vec_wrap(AllocRingBuffer::new(2));
@@ -550,14 +902,56 @@ mod tests {
         b.push(2);
         b.push(3);
 
-        assert_eq!(vec![1, 2, 3], b.to_vec())
+        assert_eq!(vec![1, 2, 3], b.to_vec());
+    }
+
+    #[test]
+    fn run_test_copy_into_vec() {
+        fn test_copy_into_vec(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.copy_into_vec(), vec![]);
+
+            // wrapped at every possible offset
+            for start in 0..10 {
+                b.clear();
+                b.extend(0..start);
+                let _ = b.skip_n(start as usize);
+                b.extend(100..105);
+
+                assert_eq!(b.copy_into_vec(), b.to_vec());
+                assert_eq!(b.copy_into_vec(), vec![100, 101, 102, 103, 104]);
+            }
+        }
+
+        test_copy_into_vec(AllocRingBuffer::new(5));
+        test_copy_into_vec(AllocRingBuffer::new(6));
+        test_copy_into_vec(ConstGenericRingBuffer::<i32, 5>::new());
+        test_copy_into_vec(ConstGenericRingBuffer::<i32, 6>::new());
+        test_copy_into_vec(GrowableAllocRingBuffer::new());
+    }
+    #[test]
+    fn run_test_oldest_n() {
+        fn test_oldest_n(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.oldest_n(3), vec![]);
+
+            b.extend(0..7);
+            assert_eq!(b.oldest_n(3), vec![2, 3, 4]);
+            assert_eq!(b.oldest_n(0), vec![]);
+            assert_eq!(b.oldest_n(10), vec![2, 3, 4, 5, 6]);
+            assert_eq!(b.len(), 5);
+        }
+
+        test_oldest_n(AllocRingBuffer::new(5));
+        test_oldest_n(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3, 4, 5]);
+        assert_eq!(b.oldest_n(3), vec![1, 2, 3]);
     }
 
     #[test]
     fn run_test_index() {
         fn test_index(mut b: impl RingBuffer<i32>) {
             b.push(2);
-            assert_eq!(b[0], 2)
+            assert_eq!(b[0], 2);
         }
 
         test_index(AllocRingBuffer::new(8));
@@ -776,7 +1170,7 @@ mod tests {
         fn test_from_iterator<T: RingBuffer<i32> + FromIterator<i32>>() {
             let b: T = std::iter::repeat(1).take(1024).collect();
             assert_eq!(b.len(), 1024);
-            assert_eq!(b.to_vec(), vec![1; 1024])
+            assert_eq!(b.to_vec(), vec![1; 1024]);
         }
 
         test_from_iterator::<GrowableAllocRingBuffer<i32>>();
@@ -788,7 +1182,7 @@ mod tests {
         fn test_from_iterator_wrap<T: RingBuffer<i32> + FromIterator<i32>>() {
             let b: T = std::iter::repeat(1).take(8000).collect();
             assert_eq!(b.len(), b.capacity());
-            assert_eq!(b.to_vec(), vec![1; b.capacity()])
+            assert_eq!(b.to_vec(), vec![1; b.capacity()]);
         }
 
         test_from_iterator_wrap::<GrowableAllocRingBuffer<i32>>();
@@ -834,6 +1228,29 @@ mod tests {
         test_contains(ConstGenericRingBuffer::<i32, 8>::new());
     }
 
+    #[test]
+    fn run_test_contains_matches_iter() {
+        fn test_contains(mut b: impl RingBuffer<u8>) {
+            // every offset of the readptr, so the elements wrap at every possible position
+            for offset in 0..8 {
+                b.clear();
+                b.extend(0..offset);
+                let _ = b.skip_n(offset as usize);
+                for len in 0..7 {
+                    b.push(len + 10);
+                    for elem in 0..20 {
+                        assert_eq!(b.contains(&elem), b.iter().any(|i| *i == elem));
+                    }
+                }
+            }
+        }
+
+        test_contains(AllocRingBuffer::new(7));
+        test_contains(AllocRingBuffer::new(8));
+        test_contains(ConstGenericRingBuffer::<u8, 7>::new());
+        test_contains(GrowableAllocRingBuffer::with_capacity(7));
+    }
+
     #[test]
     fn run_test_is_full() {
         fn test_is_full(mut b: impl RingBuffer<i32>) {
@@ -899,6 +1316,34 @@ mod tests {
         test_back_none(ConstGenericRingBuffer::<i32, 8>::new());
     }
 
+    #[test]
+    fn run_test_first_last() {
+        fn test_first_last(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.first(), None);
+            assert_eq!(b.last(), None);
+            assert_eq!(b.first_mut(), None);
+            assert_eq!(b.last_mut(), None);
+
+            b.extend([1, 2, 3]);
+            assert_eq!(b.first(), b.front());
+            assert_eq!(b.last(), b.back());
+            assert_eq!(b.first(), Some(&2));
+            assert_eq!(b.last(), Some(&3));
+
+            *b.first_mut().unwrap() = 20;
+            *b.last_mut().unwrap() = 30;
+            assert_eq!(b.to_vec(), vec![20, 30]);
+            assert_eq!(b.dequeue(), Some(20));
+        }
+
+        test_first_last(AllocRingBuffer::new(2));
+        test_first_last(ConstGenericRingBuffer::<i32, 2>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.first(), Some(&1));
+        assert_eq!(b.last_mut(), Some(&mut 3));
+    }
+
     #[test]
     fn run_test_front_some_mut() {
         fn test_front_some_mut(mut b: impl RingBuffer<i32>) {
@@ -981,7 +1426,7 @@ mod tests {
             b.skip();
             b.skip();
 
-            assert_eq!(b.len(), 0)
+            assert_eq!(b.len(), 0);
         }
 
         test_skip(AllocRingBuffer::new(8));
@@ -1007,6 +1452,55 @@ mod tests {
         test_skip2(ConstGenericRingBuffer::<i32, 2>::new());
     }
 
+    #[test]
+    fn run_test_skip_n() {
+        use std::rc::Rc;
+
+        fn test_skip_n<RB: RingBuffer<Rc<()>>>(mut b: RB) {
+            let counter = Rc::new(());
+            assert_eq!(b.skip_n(2), 0);
+
+            // wraps around the end of the storage
+            b.extend((0..6).map(|_| Rc::clone(&counter)));
+            assert_eq!(b.len(), 4);
+            assert_eq!(Rc::strong_count(&counter), 5);
+
+            assert_eq!(b.skip_n(0), 0);
+            assert_eq!(b.skip_n(3), 3);
+            assert_eq!(b.len(), 1);
+            assert_eq!(Rc::strong_count(&counter), 2);
+
+            assert_eq!(b.skip_n(3), 1);
+            assert!(b.is_empty());
+            assert_eq!(Rc::strong_count(&counter), 1);
+
+            b.push(Rc::clone(&counter));
+            assert_eq!(b.len(), 1);
+            drop(b);
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+
+        test_skip_n(AllocRingBuffer::new(4));
+        test_skip_n(ConstGenericRingBuffer::<_, 4>::new());
+
+        fn test_skip_n_copy(mut b: impl RingBuffer<i32>) {
+            b.extend(0..6);
+            assert_eq!(b.skip_n(3), 3);
+            assert_eq!(b.to_vec(), vec![5]);
+            b.extend([6, 7, 8, 9]);
+            assert_eq!(b.to_vec(), vec![6, 7, 8, 9]);
+            assert_eq!(b.skip_n(10), 4);
+            assert_eq!(b.dequeue(), None);
+        }
+
+        test_skip_n_copy(AllocRingBuffer::new(4));
+        test_skip_n_copy(ConstGenericRingBuffer::<_, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.skip_n(2), 2);
+        assert_eq!(b.to_vec(), vec![3]);
+    }
+
     #[test]
     fn run_test_push_dequeue_push() {
         fn test_push_dequeue_push(mut b: impl RingBuffer<i32>) {
@@ -1189,7 +1683,7 @@ mod tests {
 
         assert_eq!(b.get_signed(-1), Some(&2));
         assert_eq!(b.get_signed(-2), Some(&1));
-        assert_eq!(b.get_signed(-3), Some(&0))
+        assert_eq!(b.get_signed(-3), Some(&0));
     }
 
     #[test]
@@ -1446,4 +1940,1883 @@ mod tests {
         test_clone!(GrowableAllocRingBuffer::<_>::new());
         test_clone!(AllocRingBuffer::<_>::new(4));
     }
+
+    #[test]
+    fn run_test_rotate_left() {
+        fn test_rotate_left(mut b: impl RingBuffer<i32>) {
+            b.rotate_left(3);
+            assert!(b.is_empty());
+
+            b.extend([1, 2, 3, 4]);
+            let second = *b.get(1).unwrap();
+            b.rotate_left(1);
+            assert_eq!(b.get(0), Some(&second));
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 1]);
+
+            // n larger than the length wraps around
+            b.rotate_left(6);
+            assert_eq!(b.to_vec(), vec![4, 1, 2, 3]);
+            b.rotate_left(4);
+            assert_eq!(b.to_vec(), vec![4, 1, 2, 3]);
+        }
+
+        test_rotate_left(AllocRingBuffer::new(8));
+        test_rotate_left(AllocRingBuffer::new(4));
+        test_rotate_left(AllocRingBuffer::new(5));
+        test_rotate_left(GrowableAllocRingBuffer::with_capacity(8));
+        test_rotate_left(ConstGenericRingBuffer::<i32, 8>::new());
+        test_rotate_left(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_rotate_right() {
+        fn test_rotate_right(mut b: impl RingBuffer<i32>) {
+            b.rotate_right(3);
+            assert!(b.is_empty());
+
+            b.extend([1, 2, 3, 4]);
+            b.rotate_right(1);
+            assert_eq!(b.to_vec(), vec![4, 1, 2, 3]);
+
+            // n larger than the length wraps around
+            b.rotate_right(7);
+            assert_eq!(b.to_vec(), vec![1, 2, 3, 4]);
+            b.rotate_right(8);
+            assert_eq!(b.to_vec(), vec![1, 2, 3, 4]);
+        }
+
+        test_rotate_right(AllocRingBuffer::new(8));
+        test_rotate_right(AllocRingBuffer::new(4));
+        test_rotate_right(GrowableAllocRingBuffer::with_capacity(8));
+        test_rotate_right(ConstGenericRingBuffer::<i32, 8>::new());
+        test_rotate_right(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_map() {
+        struct Raw(i32);
+
+        let mut b = AllocRingBuffer::new(3);
+        b.extend((0..5).map(Raw));
+        let mapped = b.map(|r| r.0 * 2);
+        assert_eq!(mapped.capacity(), 3);
+        assert_eq!(mapped.to_vec(), vec![4, 6, 8]);
+
+        let mut b = ConstGenericRingBuffer::<_, 3>::new();
+        b.extend((0..5).map(Raw));
+        let mapped: ConstGenericRingBuffer<i32, 3> = b.map(|r| r.0 * 2);
+        assert_eq!(mapped.to_vec(), vec![4, 6, 8]);
+
+        let b: GrowableAllocRingBuffer<_> = (0..5).map(Raw).collect();
+        let mapped = b.map(|r| r.0 * 2);
+        assert_eq!(mapped.to_vec(), vec![0, 2, 4, 6, 8]);
+
+        // dropping the mapped values doesn't leak or double drop
+        let counter = std::rc::Rc::new(());
+        let mut b = AllocRingBuffer::new(2);
+        b.extend((0..3).map(|_| counter.clone()));
+        let mapped = b.map(Some);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 3);
+        drop(mapped);
+        assert_eq!(std::rc::Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn run_test_rotate_until() {
+        fn test_rotate_until(mut b: impl RingBuffer<i32>) {
+            assert!(!b.rotate_until(|_| true));
+
+            b.extend(0..7);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            assert!(b.rotate_until(|&i| i % 2 == 1));
+            assert_eq!(b.to_vec(), vec![3, 4, 5, 6, 2]);
+            assert_eq!(b.front(), Some(&3));
+
+            // the front already matches
+            assert!(b.rotate_until(|&i| i == 3));
+            assert_eq!(b.to_vec(), vec![3, 4, 5, 6, 2]);
+
+            assert!(!b.rotate_until(|&i| i > 10));
+            assert_eq!(b.to_vec(), vec![3, 4, 5, 6, 2]);
+
+            assert!(b.rotate_until(|&i| i == 2));
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6]);
+        }
+
+        test_rotate_until(AllocRingBuffer::new(5));
+        test_rotate_until(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert!(b.rotate_until(|&i| i == 3));
+        assert_eq!(b.to_vec(), vec![3, 1, 2]);
+    }
+
+    #[test]
+    fn run_test_rotate_wrapped() {
+        fn test_rotate_wrapped(mut b: impl RingBuffer<i32>) {
+            // wrap the buffer around a couple of times
+            for i in 0..11 {
+                b.push(i);
+            }
+            assert_eq!(b.to_vec(), vec![7, 8, 9, 10]);
+
+            b.rotate_left(1);
+            assert_eq!(b.to_vec(), vec![8, 9, 10, 7]);
+            b.rotate_right(2);
+            assert_eq!(b.to_vec(), vec![10, 7, 8, 9]);
+
+            // after rotation, pushing still evicts the front
+            b.push(11);
+            assert_eq!(b.to_vec(), vec![7, 8, 9, 11]);
+
+            // partially filled and wrapped
+            let _ = b.dequeue();
+            b.rotate_left(5);
+            assert_eq!(b.to_vec(), vec![11, 8, 9]);
+            b.rotate_right(4);
+            assert_eq!(b.to_vec(), vec![9, 11, 8]);
+        }
+
+        test_rotate_wrapped(AllocRingBuffer::new(4));
+        test_rotate_wrapped(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn run_test_partition_point() {
+        fn test_partition_point(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.partition_point(|&i| i < 5), 0);
+
+            // wraps around the end of the storage
+            b.extend([0, 0, 1, 2, 3, 10, 11]);
+            assert_eq!(b.to_vec(), vec![1, 2, 3, 10, 11]);
+
+            assert_eq!(b.partition_point(|&i| i < 5), 3);
+            assert_eq!(b.partition_point(|&i| i < 0), 0);
+            assert_eq!(b.partition_point(|&i| i < 100), 5);
+            assert_eq!(b.partition_point(|&i| i <= 10), 4);
+        }
+
+        test_partition_point(AllocRingBuffer::new(5));
+        test_partition_point(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3, 10, 11]);
+        assert_eq!(b.partition_point(|&i| i < 5), 3);
+    }
+
+    #[test]
+    fn run_test_binary_search() {
+        fn test_binary_search(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.binary_search(&1), Err(0));
+
+            // wraps around the end of the storage
+            b.extend([0, 0, 1, 2, 3, 10, 11]);
+            assert_eq!(b.to_vec(), vec![1, 2, 3, 10, 11]);
+
+            assert_eq!(b.binary_search(&1), Ok(0));
+            assert_eq!(b.binary_search(&10), Ok(3));
+            assert_eq!(b.binary_search(&11), Ok(4));
+            assert_eq!(b.binary_search(&0), Err(0));
+            assert_eq!(b.binary_search(&5), Err(3));
+            assert_eq!(b.binary_search(&12), Err(5));
+
+            assert_eq!(b.binary_search_by(|i| i.cmp(&3)), Ok(2));
+            assert_eq!(b.binary_search_by_key(&20, |i| i * 2), Ok(3));
+            assert_eq!(b.binary_search_by_key(&7, |i| i * 2), Err(3));
+        }
+
+        test_binary_search(AllocRingBuffer::new(5));
+        test_binary_search(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3, 10, 11]);
+        assert_eq!(b.binary_search(&3), Ok(2));
+    }
+
+    #[test]
+    fn run_test_position_find() {
+        fn test_position_find(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.position(|&i| i == 1), None);
+            assert_eq!(b.find(|&i| i == 1), None);
+
+            // wraps, leaving [3, 4, 5, 6]
+            b.extend([1, 2, 3, 4, 5, 6]);
+
+            assert_eq!(b.position(|&i| i % 2 == 0), Some(1));
+            assert_eq!(b.find(|&i| i % 2 == 0), Some(&4));
+            assert_eq!(b.position(|&i| i == 1), None);
+            assert_eq!(b.find(|&i| i > 10), None);
+
+            let index = b.position(|&i| i == 5).unwrap();
+            *b.get_mut(index).unwrap() = 50;
+            assert_eq!(b.to_vec(), vec![3, 4, 50, 6]);
+        }
+
+        test_position_find(AllocRingBuffer::new(4));
+        test_position_find(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::with_capacity(4);
+        b.extend([1, 2, 3]);
+        assert_eq!(b.position(|&i| i == 3), Some(2));
+        assert_eq!(b.find(|&i| i > 1), Some(&2));
+    }
+
+    #[test]
+    fn test_debug() {
+        use std::format;
+
+        let mut a = AllocRingBuffer::new(4);
+        a.extend([1, 2, 3, 4, 5]);
+        assert_eq!(
+            format!("{:?}", a),
+            "AllocRingBuffer { capacity: 4, len: 4, data: [2, 3, 4, 5] }"
+        );
+
+        let mut c = ConstGenericRingBuffer::<_, 4>::new();
+        c.extend([1, 2, 3]);
+        assert_eq!(
+            format!("{:?}", c),
+            "ConstGenericRingBuffer { capacity: 4, len: 3, data: [1, 2, 3] }"
+        );
+
+        let mut g = GrowableAllocRingBuffer::with_capacity(4);
+        g.extend([1, 2]);
+        assert_eq!(
+            format!("{:?}", g),
+            format!(
+                "GrowableAllocRingBuffer {{ capacity: {}, len: 2, data: [1, 2] }}",
+                g.capacity()
+            )
+        );
+    }
+
+    #[test]
+    fn test_debug_elides_long_buffers() {
+        use std::format;
+
+        let mut a = AllocRingBuffer::new(32);
+        a.extend(0..40);
+        assert_eq!(
+            format!("{:?}", a),
+            "AllocRingBuffer { capacity: 32, len: 32, data: \
+             [8, 9, 10, 11, 12, 13, 14, 15, .., 32, 33, 34, 35, 36, 37, 38, 39] }"
+        );
+    }
+
+    #[test]
+    fn test_into_vec() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let items = || (0..5).map(|_| Rc::clone(&counter));
+
+        let mut b = AllocRingBuffer::new(3);
+        b.extend(items());
+        let v = b.into_vec();
+        assert_eq!(v.len(), 3);
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(v);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let mut b = ConstGenericRingBuffer::<_, 3>::new();
+        b.extend(items());
+        let v = b.into_vec();
+        assert_eq!(v.len(), 3);
+        assert_eq!(Rc::strong_count(&counter), 4);
+        drop(v);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let mut b = GrowableAllocRingBuffer::new();
+        b.extend(items());
+        let v = b.into_vec();
+        assert_eq!(v.len(), 5);
+        assert_eq!(Rc::strong_count(&counter), 6);
+        drop(v);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let mut b = AllocRingBuffer::new(4);
+        b.extend(0..6);
+        assert_eq!(b.into_vec(), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn run_test_eq_slice() {
+        fn test_eq_slice(mut b: impl RingBuffer<i32>) {
+            assert!(b.eq_slice(&[]));
+            assert!(!b.eq_slice(&[1]));
+
+            b.extend([1, 2, 3, 4, 5]);
+            assert!(b.eq_slice(&[2, 3, 4, 5]));
+
+            // different length
+            assert!(!b.eq_slice(&[2, 3, 4]));
+            assert!(!b.eq_slice(&[2, 3, 4, 5, 6]));
+            // different content
+            assert!(!b.eq_slice(&[2, 3, 4, 6]));
+            assert!(!b.eq_slice(&[5, 4, 3, 2]));
+        }
+
+        test_eq_slice(AllocRingBuffer::new(4));
+        test_eq_slice(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert!(b.eq_slice(&[1, 2, 3]));
+    }
+
+    #[test]
+    fn run_test_contains_seq() {
+        fn test_contains_seq(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.contains_seq(&[]), Some(0));
+            assert_eq!(b.contains_seq(&[1]), None);
+
+            b.extend([9, 9, 9, 1, 2, 3, 1, 2]);
+            // the contents of the ConstGenericRingBuffer now wrap around the end of its storage
+            assert_eq!(b.to_vec(), vec![9, 1, 2, 3, 1, 2]);
+
+            assert_eq!(b.contains_seq(&[1, 2]), Some(1));
+            assert_eq!(b.contains_seq(&[3, 1, 2]), Some(3));
+            assert_eq!(b.contains_seq(&[9, 1, 2, 3, 1, 2]), Some(0));
+            assert_eq!(b.contains_seq(&[2, 1]), None);
+            assert_eq!(b.contains_seq(&[1, 2, 3, 1, 2, 3, 4]), None);
+        }
+
+        test_contains_seq(AllocRingBuffer::new(6));
+        test_contains_seq(ConstGenericRingBuffer::<i32, 6>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.contains_seq(&[2, 3]), Some(1));
+    }
+
+    #[test]
+    fn run_test_retain_newest() {
+        fn test_retain_newest(mut b: impl RingBuffer<i32>) {
+            b.extend([1, 2, 3, 4, 5, 6]);
+            b.retain_newest(2, |i| i % 2 == 0);
+            assert_eq!(b.to_vec(), vec![4, 6]);
+
+            // fewer matches than max keeps all of them
+            b.extend([7, 8, 9]);
+            b.retain_newest(10, |&i| i > 5);
+            assert_eq!(b.to_vec(), vec![6, 7, 8, 9]);
+
+            b.retain_newest(0, |_| true);
+            assert!(b.is_empty());
+        }
+
+        test_retain_newest(AllocRingBuffer::new(6));
+        test_retain_newest(AllocRingBuffer::new(8));
+        test_retain_newest(GrowableAllocRingBuffer::with_capacity(6));
+        test_retain_newest(ConstGenericRingBuffer::<i32, 6>::new());
+    }
+
+    #[test]
+    fn run_test_retain() {
+        use std::rc::Rc;
+
+        fn test_retain<RB: RingBuffer<(i32, Rc<()>)>>(mut b: RB) {
+            let counter = Rc::new(());
+            // wraps around the end of the storage
+            b.extend((0..8).map(|i| (i, Rc::clone(&counter))));
+            assert_eq!(b.len(), 6);
+            assert_eq!(Rc::strong_count(&counter), 7);
+
+            let mut calls = 0;
+            b.retain_mut(|(i, _)| {
+                calls += 1;
+                *i *= 10;
+                *i % 20 == 0
+            });
+            assert_eq!(calls, 6);
+            assert_eq!(
+                b.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+                vec![20, 40, 60]
+            );
+            assert_eq!(Rc::strong_count(&counter), 4);
+
+            b.retain(|(i, _)| *i > 20);
+            assert_eq!(b.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![40, 60]);
+            assert_eq!(Rc::strong_count(&counter), 3);
+
+            // there is room for new elements again
+            b.push((70, Rc::clone(&counter)));
+            assert_eq!(b.back().map(|(i, _)| *i), Some(70));
+
+            b.retain_mut(|_| false);
+            assert!(b.is_empty());
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+
+        test_retain(AllocRingBuffer::new(6));
+        test_retain(ConstGenericRingBuffer::<_, 6>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3, 4]);
+        b.retain_mut(|i| {
+            *i += 1;
+            *i % 2 == 0
+        });
+        assert_eq!(b.to_vec(), vec![2, 4]);
+    }
+
+    #[test]
+    fn run_test_get_from_back() {
+        fn test_get_from_back(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.get_from_back(0), None);
+            assert_eq!(b.get_from_back_mut(0), None);
+
+            b.extend([1, 2, 3, 4, 5]);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5]);
+
+            assert_eq!(b.get_from_back(0), b.back());
+            assert_eq!(b.get_from_back(0), Some(&5));
+            assert_eq!(b.get_from_back(1), Some(&4));
+            assert_eq!(b.get_from_back(3), b.front());
+            assert_eq!(b.get_from_back(4), None);
+
+            *b.get_from_back_mut(1).unwrap() = 40;
+            assert_eq!(b.to_vec(), vec![2, 3, 40, 5]);
+            assert_eq!(b.get_from_back_mut(4), None);
+        }
+
+        test_get_from_back(AllocRingBuffer::new(4));
+        test_get_from_back(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.get_from_back(1), Some(&2));
+        *b.get_from_back_mut(0).unwrap() = 30;
+        assert_eq!(b.to_vec(), vec![1, 2, 30]);
+    }
+
+    #[test]
+    fn run_test_push_all_reporting() {
+        fn test_push_all_reporting(mut b: impl RingBuffer<i32>) {
+            let report = b.push_all_reporting(0..2);
+            assert_eq!(report.pushed, 2);
+            assert_eq!(report.rejected, 0);
+            assert_eq!(report.fill_ratio, 0.5);
+
+            let report = b.push_all_reporting(2..10);
+            assert_eq!(report.pushed, 2);
+            assert_eq!(report.rejected, 6);
+            assert_eq!(report.fill_ratio, 1.0);
+            assert_eq!(b.to_vec(), vec![0, 1, 2, 3]);
+
+            let _ = b.dequeue();
+            let report = b.push_all_reporting([]);
+            assert_eq!(report.pushed, 0);
+            assert_eq!(report.fill_ratio, 0.75);
+        }
+
+        test_push_all_reporting(AllocRingBuffer::new(4));
+        test_push_all_reporting(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::new();
+        let report = b.push_all_reporting(0..10);
+        assert_eq!(report.pushed, 10);
+        assert_eq!(report.rejected, 0);
+        assert!(report.fill_ratio > 0.0 && report.fill_ratio <= 1.0);
+    }
+
+    #[test]
+    fn run_test_pop_into_while() {
+        fn test_pop_into_while(mut b: impl RingBuffer<i32>) {
+            let mut out = [0; 3];
+            assert_eq!(b.pop_into_while(&mut out, |_| true), 0);
+
+            b.extend([7, 1, 2, 3, 4, 9, 1]);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 9, 1]);
+
+            // stops when out is full
+            assert_eq!(b.pop_into_while(&mut out[..2], |&i| i < 5), 2);
+            assert_eq!(out, [2, 3, 0]);
+
+            // stops at the first item not matching
+            assert_eq!(b.pop_into_while(&mut out, |&i| i < 5), 1);
+            assert_eq!(out, [4, 3, 0]);
+            assert_eq!(b.to_vec(), vec![9, 1]);
+
+            assert_eq!(b.pop_into_while(&mut out, |_| true), 2);
+            assert_eq!(out, [9, 1, 0]);
+            assert!(b.is_empty());
+        }
+
+        test_pop_into_while(AllocRingBuffer::new(5));
+        test_pop_into_while(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        let mut out = [0; 3];
+        assert_eq!(b.pop_into_while(&mut out, |&i| i != 3), 2);
+        assert_eq!(out, [1, 2, 0]);
+    }
+
+    #[test]
+    fn run_test_push_force_pop() {
+        fn test_push_force_pop(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.push_force_pop(1), None);
+            assert_eq!(b.push_force_pop(2), None);
+            assert_eq!(b.push_force_pop(3), None);
+            assert_eq!(b.push_force_pop(4), Some(1));
+            assert_eq!(b.push_force_pop(5), Some(2));
+            assert_eq!(b.to_vec(), vec![3, 4, 5]);
+
+            let _ = b.dequeue();
+            assert_eq!(b.push_force_pop(6), None);
+            assert_eq!(b.to_vec(), vec![4, 5, 6]);
+        }
+
+        test_push_force_pop(AllocRingBuffer::new(3));
+        test_push_force_pop(ConstGenericRingBuffer::<i32, 3>::new());
+
+        let mut b = GrowableAllocRingBuffer::with_capacity(1);
+        assert_eq!(b.push_force_pop(1), None);
+        assert_eq!(b.push_force_pop(2), None);
+        assert_eq!(b.to_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn run_test_saturating_push() {
+        fn test_saturating_push(mut b: impl RingBuffer<i32>) {
+            assert!(!b.saturating_push(1));
+            assert!(!b.saturating_push(2));
+            assert!(!b.saturating_push(3));
+            assert!(b.saturating_push(4));
+            assert!(b.saturating_push(5));
+            assert_eq!(b.to_vec(), vec![3, 4, 5]);
+
+            let _ = b.dequeue();
+            assert!(!b.saturating_push(6));
+            assert_eq!(b.to_vec(), vec![4, 5, 6]);
+        }
+
+        test_saturating_push(AllocRingBuffer::new(3));
+        test_saturating_push(ConstGenericRingBuffer::<i32, 3>::new());
+
+        let mut b = GrowableAllocRingBuffer::with_capacity(1);
+        assert!(!b.saturating_push(1));
+        assert!(!b.saturating_push(2));
+        assert_eq!(b.to_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn run_test_copy_to_slice() {
+        fn test_copy_to_slice(mut b: impl RingBuffer<i32>) {
+            let mut out = [0; 8];
+            assert_eq!(b.copy_to_slice(&mut out), 0);
+
+            // wrapped at every possible offset
+            for start in 0..10 {
+                b.clear();
+                b.extend(0..start);
+                for _ in 0..start {
+                    let _ = b.dequeue();
+                }
+                b.extend(100..105);
+
+                let mut out = [0; 8];
+                assert_eq!(b.copy_to_slice(&mut out), 5);
+                assert_eq!(out, [100, 101, 102, 103, 104, 0, 0, 0]);
+
+                let mut out = [0; 3];
+                assert_eq!(b.copy_to_slice(&mut out), 3);
+                assert_eq!(out, [100, 101, 102]);
+
+                assert_eq!(b.to_vec(), vec![100, 101, 102, 103, 104]);
+            }
+        }
+
+        test_copy_to_slice(AllocRingBuffer::new(5));
+        test_copy_to_slice(AllocRingBuffer::new(6));
+        test_copy_to_slice(ConstGenericRingBuffer::<i32, 5>::new());
+        test_copy_to_slice(ConstGenericRingBuffer::<i32, 6>::new());
+        test_copy_to_slice(GrowableAllocRingBuffer::new());
+    }
+
+    #[test]
+    fn run_test_fold_regions() {
+        fn test_fold_regions(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.as_slices(), (&[][..], &[][..]));
+            assert_eq!(b.fold_regions(0, |calls, _| calls + 1), 1);
+
+            // wrapped at every possible offset
+            for start in 0..10 {
+                b.clear();
+                b.extend(0..start);
+                for _ in 0..start {
+                    let _ = b.dequeue();
+                }
+                b.extend(100..105);
+
+                let (head, tail) = b.as_slices();
+                assert_eq!([head, tail].concat(), vec![100, 101, 102, 103, 104]);
+                assert_eq!(tail.is_empty(), b.is_contiguous());
+                let (head_len, tail_len) = (head.len(), tail.len());
+                let (head, tail) = b.as_mut_slices();
+                assert_eq!((head.len(), tail.len()), (head_len, tail_len));
+                assert_eq!([&*head, &*tail].concat(), vec![100, 101, 102, 103, 104]);
+
+                let regions = b.fold_regions(Vec::new(), |mut acc, region| {
+                    acc.push(region.to_vec());
+                    acc
+                });
+                assert_eq!(regions.concat(), b.to_vec());
+                assert_eq!(regions.len(), if b.is_contiguous() { 1 } else { 2 });
+            }
+        }
+
+        test_fold_regions(AllocRingBuffer::new(5));
+        test_fold_regions(AllocRingBuffer::new(6));
+        test_fold_regions(ConstGenericRingBuffer::<i32, 5>::new());
+        test_fold_regions(ConstGenericRingBuffer::<i32, 6>::new());
+        test_fold_regions(GrowableAllocRingBuffer::new());
+    }
+
+    #[test]
+    fn run_test_flush_to() {
+        fn test_flush_to(mut b: impl RingBuffer<i32>) {
+            let mut calls = 0;
+            b.flush_to(|_| calls += 1);
+            assert_eq!(calls, 1);
+
+            // wrapped at every possible offset
+            for start in 0..10 {
+                b.clear();
+                b.extend(0..start);
+                let _ = b.skip_n(start as usize);
+                b.extend(100..105);
+
+                let expected = b.to_vec();
+                let mut flushed = Vec::new();
+                let mut calls = 0;
+                b.flush_to(|region| {
+                    flushed.extend_from_slice(region);
+                    calls += 1;
+                });
+                assert_eq!(flushed, expected);
+                assert!(calls <= 2);
+                assert!(b.is_empty());
+
+                // the buffer is usable afterwards
+                b.push(1);
+                assert_eq!(b.to_vec(), vec![1]);
+            }
+        }
+
+        test_flush_to(AllocRingBuffer::new(5));
+        test_flush_to(AllocRingBuffer::new(6));
+        test_flush_to(ConstGenericRingBuffer::<i32, 5>::new());
+        test_flush_to(ConstGenericRingBuffer::<i32, 6>::new());
+        test_flush_to(GrowableAllocRingBuffer::new());
+    }
+
+    #[test]
+    fn run_test_is_contiguous() {
+        fn test_is_contiguous(mut b: impl RingBuffer<i32>) {
+            assert!(b.is_contiguous());
+
+            b.extend(0..4);
+            assert!(b.is_contiguous());
+
+            // the write pointer wraps around, the read pointer is at slot 1
+            b.push(4);
+            assert!(!b.is_contiguous());
+
+            // the read pointer wraps around as well
+            for _ in 0..3 {
+                let _ = b.dequeue();
+            }
+            assert!(b.is_contiguous());
+
+            b.clear();
+            assert!(b.is_contiguous());
+        }
+
+        test_is_contiguous(AllocRingBuffer::new(4));
+        test_is_contiguous(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::with_capacity(4);
+        b.extend(0..4);
+        assert!(b.is_contiguous());
+        let _ = b.dequeue();
+        let _ = b.dequeue();
+        b.extend(4..6);
+        assert_eq!(b.is_contiguous(), b.as_slices().1.is_empty());
+    }
+
+    #[test]
+    fn run_test_extend_from_slice() {
+        fn test_extend_from_slice(mut b: impl RingBuffer<i32> + Clone) {
+            let items: Vec<i32> = (100..120).collect();
+
+            // start at every possible offset and length, then compare against pushing one by one
+            for prefill in 0..12 {
+                for n in 0..items.len() {
+                    b.clear();
+                    b.extend(0..prefill);
+                    let mut expected = b.clone();
+
+                    b.extend_from_slice(&items[..n]);
+                    for &i in &items[..n] {
+                        expected.push(i);
+                    }
+
+                    assert_eq!(
+                        b.to_vec(),
+                        expected.to_vec(),
+                        "prefill {}, n {}",
+                        prefill,
+                        n
+                    );
+                }
+            }
+
+            // the result equals the last `capacity` items of everything that was pushed
+            b.clear();
+            let all: Vec<i32> = (0..23).collect();
+            for chunk in all.chunks(4) {
+                b.extend_from_slice(chunk);
+            }
+            assert_eq!(b.to_vec(), all[all.len() - b.capacity()..].to_vec());
+        }
+
+        test_extend_from_slice(AllocRingBuffer::new(5));
+        test_extend_from_slice(AllocRingBuffer::new(8));
+        test_extend_from_slice(ConstGenericRingBuffer::<i32, 5>::new());
+        test_extend_from_slice(ConstGenericRingBuffer::<i32, 8>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2]);
+        b.extend_from_slice(&[3, 4, 5]);
+        assert_eq!(b.to_vec(), vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn run_test_split_off() {
+        use std::rc::Rc;
+
+        macro_rules! test_split_off {
+            ($constructor: expr) => {{
+                let counter = Rc::new(());
+                let mut b = $constructor;
+                // 0 and 1 are overwritten, so the elements wrap around the end of the storage
+                b.extend((0..7).map(|i| (i, Rc::clone(&counter))));
+                assert_eq!(Rc::strong_count(&counter), 6);
+
+                let tail = b.split_off(2);
+                assert_eq!(b.iter().map(|i| i.0).collect::<Vec<_>>(), vec![2, 3]);
+                assert_eq!(tail.iter().map(|i| i.0).collect::<Vec<_>>(), vec![4, 5, 6]);
+                assert_eq!(tail.capacity(), b.capacity());
+                // nothing was cloned or dropped
+                assert_eq!(Rc::strong_count(&counter), 6);
+
+                let empty = b.split_off(2);
+                assert!(empty.is_empty());
+                let all = b.split_off(0);
+                assert!(b.is_empty());
+                assert_eq!(all.len(), 2);
+
+                drop((b, tail, empty, all));
+                assert_eq!(Rc::strong_count(&counter), 1);
+            }};
+        }
+
+        test_split_off!(AllocRingBuffer::new(5));
+        test_split_off!(ConstGenericRingBuffer::<_, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3, 4, 5]);
+        let tail = b.split_off(2);
+        assert_eq!(b.to_vec(), vec![1, 2]);
+        assert_eq!(tail.to_vec(), vec![3, 4, 5]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_split_off_out_of_bounds() {
+        let mut b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.split_off(4);
+    }
+
+    #[test]
+    fn run_test_append() {
+        use std::rc::Rc;
+
+        fn test_append(mut b: impl RingBuffer<Rc<i32>>) {
+            let mut other = GrowableAllocRingBuffer::new();
+            other.extend((0..3).map(Rc::new));
+            let first = Rc::clone(&other[0]);
+
+            b.append(&mut other);
+            assert!(other.is_empty());
+            assert_eq!(b.iter().map(|i| **i).collect::<Vec<_>>(), vec![0, 1, 2]);
+            // moved, not cloned
+            assert!(Rc::ptr_eq(&b[0], &first));
+
+            let mut other = AllocRingBuffer::new(8);
+            other.extend((3..9).map(Rc::new));
+            b.append(&mut other);
+            assert!(other.is_empty());
+            assert_eq!(b.iter().map(|i| **i).collect::<Vec<_>>(), vec![5, 6, 7, 8]);
+            assert_eq!(Rc::strong_count(&first), 1);
+        }
+
+        test_append(AllocRingBuffer::new(4));
+        test_append(ConstGenericRingBuffer::<_, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1]);
+        let mut other = ConstGenericRingBuffer::<_, 2>::from([2, 3]);
+        b.append(&mut other);
+        assert_eq!(b.to_vec(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn run_test_push_force_stream() {
+        fn test_push_force_stream(mut b: impl RingBuffer<i32>) {
+            let mut stream = b.push_force_stream(0..6);
+            // the first three pushes fill the buffer, the fourth evicts 0
+            assert_eq!(stream.next(), Some(0));
+            assert_eq!(stream.next(), Some(1));
+            assert_eq!(stream.next(), Some(2));
+            assert_eq!(stream.next(), None);
+            assert_eq!(stream.next(), None);
+            assert_eq!(b.to_vec(), vec![3, 4, 5]);
+
+            // dropping the stream early leaves the remaining items unpushed
+            let mut items = 6..10;
+            assert_eq!(b.push_force_stream(items.by_ref()).next(), Some(3));
+            assert_eq!(b.to_vec(), vec![4, 5, 6]);
+            assert_eq!(items, 7..10);
+        }
+
+        test_push_force_stream(AllocRingBuffer::new(3));
+        test_push_force_stream(ConstGenericRingBuffer::<i32, 3>::new());
+
+        let mut b = GrowableAllocRingBuffer::new();
+        assert_eq!(b.push_force_stream(0..4).count(), 0);
+        assert_eq!(b.to_vec(), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn run_test_try_push() {
+        fn test_try_push(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.try_push(1), Ok(()));
+            assert_eq!(b.try_push(2), Ok(()));
+            assert_eq!(b.try_push(3), Err(3));
+            assert_eq!(b.to_vec(), vec![1, 2]);
+
+            let _ = b.dequeue();
+            assert_eq!(b.try_push(4), Ok(()));
+            assert_eq!(b.to_vec(), vec![2, 4]);
+        }
+
+        test_try_push(AllocRingBuffer::new(2));
+        test_try_push(ConstGenericRingBuffer::<i32, 2>::new());
+
+        let mut b = GrowableAllocRingBuffer::with_capacity(1);
+        assert_eq!(b.try_push(1), Ok(()));
+        assert_eq!(b.try_push(2), Ok(()));
+        assert_eq!(b.to_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_ringbuffer_error() {
+        use crate::RingBufferError;
+        use std::string::ToString;
+
+        let e: std::boxed::Box<dyn std::error::Error> =
+            std::boxed::Box::new(RingBufferError::InvalidCapacity);
+        assert_eq!(e.to_string(), "Capacity must be greater than 0");
+    }
+
+    #[test]
+    fn run_test_try_get() {
+        use crate::IndexError;
+
+        fn test_try_get(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.try_get(0), Err(IndexError::Empty { index: 0 }));
+            assert_eq!(b.try_get_mut(3), Err(IndexError::Empty { index: 3 }));
+
+            // wraps around the end of the storage
+            b.extend(0..6);
+            assert_eq!(b.try_get(0), Ok(&2));
+            assert_eq!(b.try_get(3), Ok(&5));
+            assert_eq!(
+                b.try_get(4),
+                Err(IndexError::OutOfBounds { index: 4, len: 4 })
+            );
+
+            *b.try_get_mut(1).unwrap() = 30;
+            assert_eq!(b.to_vec(), vec![2, 30, 4, 5]);
+            assert_eq!(
+                b.try_get_mut(usize::MAX),
+                Err(IndexError::OutOfBounds {
+                    index: usize::MAX,
+                    len: 4
+                })
+            );
+        }
+
+        test_try_get(AllocRingBuffer::new(4));
+        test_try_get(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2]);
+        assert_eq!(b.try_get(1), Ok(&2));
+        assert_eq!(
+            b.try_get(2),
+            Err(IndexError::OutOfBounds { index: 2, len: 2 })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn test_index_error() {
+        use crate::IndexError;
+        use std::string::ToString;
+
+        assert_eq!(
+            IndexError::Empty { index: 1 }.to_string(),
+            "index 1 is out of bounds, the ringbuffer is empty"
+        );
+        let e: std::boxed::Box<dyn std::error::Error> =
+            std::boxed::Box::new(IndexError::OutOfBounds { index: 5, len: 3 });
+        assert_eq!(e.to_string(), "index 5 is out of bounds for length 3");
+    }
+
+    #[test]
+    fn run_test_push_overwriting() {
+        use crate::PushResult;
+
+        fn test_push_overwriting(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.push_overwriting(1), PushResult::Inserted);
+            assert_eq!(b.push_overwriting(2), PushResult::Inserted);
+            assert_eq!(b.push_overwriting(3), PushResult::Overwrote(1));
+            assert_eq!(b.to_vec(), vec![2, 3]);
+
+            let _ = b.dequeue();
+            assert_eq!(b.push_overwriting(4), PushResult::Inserted);
+            assert_eq!(b.to_vec(), vec![3, 4]);
+        }
+
+        test_push_overwriting(AllocRingBuffer::new(2));
+        test_push_overwriting(ConstGenericRingBuffer::<i32, 2>::new());
+
+        let mut b = GrowableAllocRingBuffer::with_capacity(1);
+        assert_eq!(b.push_overwriting(1), PushResult::Inserted);
+        assert_eq!(b.push_overwriting(2), PushResult::Inserted);
+        assert_eq!(b.to_vec(), vec![1, 2]);
+    }
+
+    #[test]
+    fn run_test_push_force_pop_moves_out() {
+        use std::rc::Rc;
+
+        fn test_push_force_pop_moves_out(mut b: impl RingBuffer<Rc<()>>) {
+            let counter = Rc::new(());
+            b.push(Rc::clone(&counter));
+            b.push(Rc::clone(&counter));
+
+            let evicted = b.push_force_pop(Rc::clone(&counter));
+            // the evicted element is handed out, not dropped
+            assert_eq!(Rc::strong_count(&counter), 4);
+            drop(evicted);
+            assert_eq!(Rc::strong_count(&counter), 3);
+        }
+
+        test_push_force_pop_moves_out(AllocRingBuffer::new(2));
+        test_push_force_pop_moves_out(ConstGenericRingBuffer::<_, 2>::new());
+    }
+
+    #[test]
+    fn run_test_clip_count() {
+        fn test_clip_count(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.clip_count(-10, 10), 0);
+
+            b.extend([-11, -10, 0, 10, 11, 100]);
+            assert_eq!(b.clip_count(-10, 10), 3);
+            assert_eq!(b.clip_count(0, 0), 5);
+            assert_eq!(b.clip_count(-100, 100), 0);
+        }
+
+        test_clip_count(AllocRingBuffer::new(6));
+        test_clip_count(GrowableAllocRingBuffer::with_capacity(6));
+        test_clip_count(ConstGenericRingBuffer::<i32, 6>::new());
+
+        let b = AllocRingBuffer::from([0.0, f32::NAN, 1.5]);
+        assert_eq!(b.clip_count(-1.0, 1.0), 2);
+    }
+
+    #[test]
+    fn run_test_copy_within() {
+        fn test_copy_within(mut b: impl RingBuffer<i32>) {
+            b.extend([0, 0, 1, 2, 3, 4]);
+            assert_eq!(b.to_vec(), vec![1, 2, 3, 4]);
+
+            b.copy_within(0..2, 1);
+            assert_eq!(b.to_vec(), vec![1, 1, 2, 4]);
+
+            b.copy_within(1..4, 0);
+            assert_eq!(b.to_vec(), vec![1, 2, 4, 4]);
+
+            b.copy_within(3..4, 0);
+            assert_eq!(b.to_vec(), vec![4, 2, 4, 4]);
+
+            b.copy_within(2..2, 4);
+            assert_eq!(b.to_vec(), vec![4, 2, 4, 4]);
+        }
+
+        test_copy_within(AllocRingBuffer::new(4));
+        test_copy_within(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        b.copy_within(1..3, 0);
+        assert_eq!(b.to_vec(), vec![2, 3, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_copy_within_out_of_bounds() {
+        let mut b = AllocRingBuffer::from([1, 2, 3, 4]);
+        b.copy_within(1..3, 3);
+    }
+
+    #[test]
+    fn run_test_insert() {
+        fn test_insert(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.insert(0, 1), Ok(()));
+            assert_eq!(b.insert(1, 3), Ok(()));
+            assert_eq!(b.insert(1, 2), Ok(()));
+            assert_eq!(b.to_vec(), vec![1, 2, 3]);
+        }
+
+        test_insert(AllocRingBuffer::new(3));
+        test_insert(GrowableAllocRingBuffer::new());
+        test_insert(ConstGenericRingBuffer::<i32, 3>::new());
+    }
+
+    #[test]
+    fn run_test_insert_wrapped() {
+        fn test_insert_wrapped(mut b: impl RingBuffer<i32>) {
+            b.extend(0..7);
+            let _ = b.dequeue();
+            let _ = b.dequeue();
+            // inserting now moves elements across the end of the storage
+            assert_eq!(b.to_vec(), vec![4, 5, 6]);
+
+            assert_eq!(b.insert(1, 10), Ok(()));
+            assert_eq!(b.to_vec(), vec![4, 10, 5, 6]);
+            assert_eq!(b.insert(4, 11), Ok(()));
+            assert_eq!(b.to_vec(), vec![4, 10, 5, 6, 11]);
+
+            assert_eq!(b.insert(0, 12), Err(12));
+            assert_eq!(b.to_vec(), vec![4, 10, 5, 6, 11]);
+        }
+
+        test_insert_wrapped(AllocRingBuffer::new(5));
+        test_insert_wrapped(ConstGenericRingBuffer::<i32, 5>::new());
+    }
+
+    #[test]
+    fn run_test_remove() {
+        fn test_remove(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.remove(0), None);
+
+            b.extend(0..7);
+            // the contents of the ConstGenericRingBuffer now straddle the end of its storage
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            assert_eq!(b.remove(3), Some(5));
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 6]);
+            assert_eq!(b.remove(4), None);
+            assert_eq!(b.remove(3), Some(6));
+            assert_eq!(b.remove(0), Some(2));
+            assert_eq!(b.to_vec(), vec![3, 4]);
+
+            b.extend([7, 8, 9]);
+            assert_eq!(b.remove(2), Some(7));
+            assert_eq!(b.to_vec(), vec![3, 4, 8, 9]);
+        }
+
+        test_remove(AllocRingBuffer::new(5));
+        test_remove(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.remove(1), Some(2));
+        assert_eq!(b.remove(2), None);
+        assert_eq!(b.to_vec(), vec![1, 3]);
+    }
+
+    #[test]
+    fn run_test_swap_remove_front() {
+        fn test_swap_remove_front(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.swap_remove_front(0), None);
+
+            b.extend(0..6);
+            assert_eq!(b.to_vec(), vec![1, 2, 3, 4, 5]);
+
+            // the oldest element fills the vacated slot
+            assert_eq!(b.swap_remove_front(3), Some(4));
+            assert_eq!(b.to_vec(), vec![2, 3, 1, 5]);
+            assert_eq!(b.swap_remove_front(0), Some(2));
+            assert_eq!(b.to_vec(), vec![3, 1, 5]);
+            assert_eq!(b.swap_remove_front(3), None);
+            assert_eq!(b.swap_remove_front(2), Some(5));
+            assert_eq!(b.to_vec(), vec![1, 3]);
+        }
+
+        test_swap_remove_front(AllocRingBuffer::new(5));
+        test_swap_remove_front(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.swap_remove_front(2), Some(3));
+        assert_eq!(b.to_vec(), vec![2, 1]);
+    }
+
+    #[test]
+    fn run_test_get_many_mut() {
+        fn test_get_many_mut(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.get_many_mut([0]), None);
+            assert_eq!(b.get_many_mut([]), Some([]));
+
+            // wraps around the end of the storage
+            b.extend(0..7);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            let [a, c] = b.get_many_mut([1, 4]).unwrap();
+            core::mem::swap(a, c);
+            *a += 10;
+            assert_eq!(b.to_vec(), vec![2, 16, 4, 5, 3]);
+
+            let [a, c, d] = b.get_many_mut([4, 0, 2]).unwrap();
+            assert_eq!([*a, *c, *d], [3, 2, 4]);
+
+            assert_eq!(b.get_many_mut([0, 5]), None);
+            assert_eq!(b.get_many_mut([3, 0, 3]), None);
+        }
+
+        test_get_many_mut(AllocRingBuffer::new(5));
+        test_get_many_mut(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        let [a, c] = b.get_many_mut([2, 0]).unwrap();
+        core::mem::swap(a, c);
+        assert_eq!(b.to_vec(), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn run_test_swap_remove_back() {
+        fn test_swap_remove_back(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.swap_remove_back(0), None);
+
+            // wraps around the end of the storage
+            b.extend(0..6);
+            assert_eq!(b.to_vec(), vec![1, 2, 3, 4, 5]);
+
+            // the newest element fills the vacated slot
+            assert_eq!(b.swap_remove_back(1), Some(2));
+            assert_eq!(b.to_vec(), vec![1, 5, 3, 4]);
+            assert_eq!(b.swap_remove_back(3), Some(4));
+            assert_eq!(b.to_vec(), vec![1, 5, 3]);
+            assert_eq!(b.swap_remove_back(3), None);
+            assert_eq!(b.swap_remove_back(0), Some(1));
+            assert_eq!(b.to_vec(), vec![3, 5]);
+
+            // pushing continues after the new back element
+            b.extend([6, 7]);
+            assert_eq!(b.to_vec(), vec![3, 5, 6, 7]);
+        }
+
+        test_swap_remove_back(AllocRingBuffer::new(5));
+        test_swap_remove_back(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.swap_remove_back(0), Some(1));
+        assert_eq!(b.to_vec(), vec![3, 2]);
+    }
+
+    #[test]
+    fn test_swap_remove_back_drops() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut b = AllocRingBuffer::new(3);
+        b.extend((0..5).map(|i| (i, Rc::clone(&counter))));
+
+        let (removed, _) = b.swap_remove_back(0).unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        assert_eq!(b.iter().map(|(i, _)| *i).collect::<Vec<_>>(), [4, 3]);
+
+        drop(b);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_insert_out_of_bounds() {
+        let mut b = AllocRingBuffer::new(4);
+        b.push(1);
+        let _ = b.insert(2, 2);
+    }
+
+    #[test]
+    fn run_test_truncate() {
+        fn test_truncate(mut b: impl RingBuffer<i32>) {
+            b.extend(0..6);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5]);
+
+            b.truncate(4);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5]);
+            b.truncate(10);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5]);
+
+            b.truncate(3);
+            assert_eq!(b.to_vec(), vec![3, 4, 5]);
+            b.push(6);
+            assert_eq!(b.to_vec(), vec![3, 4, 5, 6]);
+
+            b.truncate(0);
+            assert!(b.is_empty());
+        }
+
+        test_truncate(AllocRingBuffer::new(4));
+        test_truncate(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([0, 1, 2, 3]);
+        b.truncate(2);
+        assert_eq!(b.to_vec(), vec![2, 3]);
+    }
+
+    #[test]
+    fn run_test_truncate_front() {
+        fn test_truncate_front(mut b: impl RingBuffer<i32>) {
+            b.extend(0..6);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5]);
+
+            b.truncate_front(4);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5]);
+
+            b.truncate_front(3);
+            assert_eq!(b.to_vec(), vec![2, 3, 4]);
+            b.push(6);
+            b.push(7);
+            assert_eq!(b.to_vec(), vec![3, 4, 6, 7]);
+
+            b.truncate_front(1);
+            assert_eq!(b.to_vec(), vec![3]);
+            b.truncate_front(0);
+            assert!(b.is_empty());
+        }
+
+        test_truncate_front(AllocRingBuffer::new(4));
+        test_truncate_front(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let mut b = GrowableAllocRingBuffer::from([0, 1, 2, 3]);
+        b.truncate_front(2);
+        assert_eq!(b.to_vec(), vec![0, 1]);
+    }
+
+    #[test]
+    fn run_test_truncate_drops() {
+        use std::rc::Rc;
+
+        fn test_truncate_drops(mut b: impl RingBuffer<Rc<()>>) {
+            let counter = Rc::new(());
+            for _ in 0..4 {
+                b.push(Rc::clone(&counter));
+            }
+
+            b.truncate(3);
+            assert_eq!(Rc::strong_count(&counter), 4);
+            b.truncate_front(1);
+            assert_eq!(Rc::strong_count(&counter), 2);
+        }
+
+        test_truncate_drops(AllocRingBuffer::new(4));
+        test_truncate_drops(GrowableAllocRingBuffer::new());
+        test_truncate_drops(ConstGenericRingBuffer::<_, 4>::new());
+    }
+
+    #[test]
+    fn run_test_statistics() {
+        fn test_statistics(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.sum(), 0);
+            assert_eq!(b.mean(), None);
+            assert_eq!(b.min_element(), None);
+            assert_eq!(b.max_element(), None);
+
+            b.extend([10, 3, -4, 7, 3, 2]);
+            assert_eq!(b.to_vec(), vec![-4, 7, 3, 2]);
+            assert_eq!(b.sum(), 8);
+            assert_eq!(b.mean(), Some(2.0));
+            assert_eq!(b.min_element(), Some(&-4));
+            assert_eq!(b.max_element(), Some(&7));
+        }
+
+        test_statistics(AllocRingBuffer::new(4));
+        test_statistics(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2]);
+        assert_eq!(b.sum(), 3);
+        assert_eq!(b.mean(), Some(1.5));
+    }
+
+    #[test]
+    fn test_mean_does_not_overflow() {
+        let b = AllocRingBuffer::from([i32::MAX, i32::MAX, i32::MAX]);
+        assert_eq!(b.mean(), Some(f64::from(i32::MAX)));
+    }
+
+    #[test]
+    fn run_test_range() {
+        fn test_range(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.range(..).count(), 0);
+
+            b.extend(0..7);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            let collect = |it: RingBufferIterator<'_, i32, _>| it.copied().collect::<Vec<_>>();
+            assert_eq!(collect(b.range(1..4)), vec![3, 4, 5]);
+            assert_eq!(collect(b.range(1..=4)), vec![3, 4, 5, 6]);
+            assert_eq!(collect(b.range(..2)), vec![2, 3]);
+            assert_eq!(collect(b.range(3..)), vec![5, 6]);
+            assert_eq!(collect(b.range(..)), b.to_vec());
+            assert_eq!(collect(b.range(5..5)), vec![]);
+            assert_eq!(
+                b.range(1..4).rev().copied().collect::<Vec<_>>(),
+                vec![5, 4, 3]
+            );
+            assert_eq!(b.range(1..4).len(), 3);
+        }
+
+        test_range(AllocRingBuffer::new(5));
+        test_range(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.range(1..).copied().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    fn run_test_iter_from() {
+        fn test_iter_from(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.iter_from(0).next(), None);
+            assert_eq!(b.iter_from(3).next(), None);
+
+            // wraps around the end of the storage
+            b.extend(0..7);
+            for start in 0..8 {
+                assert_eq!(
+                    b.iter_from(start).collect::<Vec<_>>(),
+                    b.iter().skip(start).collect::<Vec<_>>()
+                );
+                assert_eq!(b.iter_from(start).len(), 5_usize.saturating_sub(start));
+            }
+            assert_eq!(b.iter_from(usize::MAX).next(), None);
+            assert_eq!(b.iter_from(3).rev().copied().collect::<Vec<_>>(), [6, 5]);
+        }
+
+        test_iter_from(AllocRingBuffer::new(5));
+        test_iter_from(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.iter_from(1).copied().collect::<Vec<_>>(), vec![2, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_range_end_out_of_bounds() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.range(1..4);
+    }
+
+    #[test]
+    #[should_panic]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_range_start_after_end() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.range(2..1);
+    }
+
+    #[test]
+    fn run_test_windows() {
+        fn test_windows(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.windows(1).count(), 0);
+
+            b.extend(0..7);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            let windows: Vec<Vec<i32>> = b.windows(3).map(|w| w.copied().collect()).collect();
+            assert_eq!(windows, vec![vec![2, 3, 4], vec![3, 4, 5], vec![4, 5, 6]]);
+
+            let mut windows = b.windows(5);
+            assert_eq!(windows.len(), 1);
+            let window = windows.next().unwrap();
+            assert_eq!(window.len(), 5);
+            assert_eq!(window.copied().collect::<Vec<_>>(), vec![2, 3, 4, 5, 6]);
+            assert!(windows.next().is_none());
+
+            assert_eq!(b.windows(6).count(), 0);
+            assert_eq!(b.windows(1).len(), 5);
+        }
+
+        test_windows(AllocRingBuffer::new(5));
+        test_windows(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.windows(2).map(|w| w.sum()).collect::<Vec<i32>>(), [3, 5]);
+    }
+
+    #[test]
+    fn run_test_for_each_window() {
+        fn test_for_each_window(mut b: impl RingBuffer<i32>) {
+            let mut calls = 0;
+            b.for_each_window(2, |_| calls += 1);
+            assert_eq!(calls, 0);
+
+            b.extend(0..7);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6]);
+
+            let mut sums = vec![];
+            b.for_each_window(3, |w| {
+                assert_eq!(w.len(), 3);
+                sums.push(w.iter().copied().sum::<i32>());
+            });
+            assert_eq!(sums, vec![9, 12, 15]);
+
+            let mut windows = vec![];
+            b.for_each_window(5, |w| {
+                windows.push(w.iter().map(|&&i| i).collect::<Vec<_>>());
+            });
+            assert_eq!(windows, vec![vec![2, 3, 4, 5, 6]]);
+        }
+
+        test_for_each_window(AllocRingBuffer::new(5));
+        test_for_each_window(ConstGenericRingBuffer::<i32, 5>::new());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_windows_zero_size() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.windows(0);
+    }
+
+    #[test]
+    fn run_test_chunks() {
+        fn test_chunks(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.chunks(2).count(), 0);
+            assert_eq!(b.chunks_exact(2).count(), 0);
+
+            b.extend(0..8);
+            assert_eq!(b.to_vec(), vec![3, 4, 5, 6, 7]);
+
+            let collect = |chunks: RingBufferChunks<'_, i32, _>| -> Vec<Vec<i32>> {
+                chunks.map(|c| c.copied().collect()).collect()
+            };
+
+            assert_eq!(b.chunks(2).len(), 3);
+            assert_eq!(collect(b.chunks(2)), vec![vec![3, 4], vec![5, 6], vec![7]]);
+            assert_eq!(b.chunks_exact(2).len(), 2);
+            assert_eq!(collect(b.chunks_exact(2)), vec![vec![3, 4], vec![5, 6]]);
+
+            assert_eq!(collect(b.chunks(5)), vec![vec![3, 4, 5, 6, 7]]);
+            assert_eq!(collect(b.chunks_exact(5)), vec![vec![3, 4, 5, 6, 7]]);
+            assert_eq!(collect(b.chunks(10)), vec![vec![3, 4, 5, 6, 7]]);
+            assert_eq!(b.chunks_exact(10).count(), 0);
+            assert_eq!(b.chunks(usize::MAX).len(), 1);
+            assert_eq!(collect(b.chunks(usize::MAX)), vec![vec![3, 4, 5, 6, 7]]);
+            assert_eq!(b.chunks_exact(usize::MAX).len(), 0);
+        }
+
+        test_chunks(AllocRingBuffer::new(5));
+        test_chunks(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.chunks(2).map(|c| c.sum()).collect::<Vec<i32>>(), [3, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_chunks_zero_size() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.chunks_exact(0);
+    }
+
+    #[test]
+    fn run_test_rchunks() {
+        fn test_rchunks(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.rchunks(2).count(), 0);
+            assert_eq!(b.iter_rev().next(), None);
+
+            b.extend(0..8);
+            assert_eq!(
+                b.iter_rev().copied().collect::<Vec<_>>(),
+                vec![7, 6, 5, 4, 3]
+            );
+
+            let collect = |chunks: RingBufferRChunks<'_, i32, _>| -> Vec<Vec<i32>> {
+                chunks.map(|c| c.copied().collect()).collect()
+            };
+
+            assert_eq!(b.rchunks(2).len(), 3);
+            assert_eq!(collect(b.rchunks(2)), vec![vec![6, 7], vec![4, 5], vec![3]]);
+            assert_eq!(b.rchunks(1).len(), 5);
+            assert_eq!(collect(b.rchunks(5)), vec![vec![3, 4, 5, 6, 7]]);
+            assert_eq!(collect(b.rchunks(10)), vec![vec![3, 4, 5, 6, 7]]);
+            assert_eq!(b.rchunks(usize::MAX).len(), 1);
+            assert_eq!(collect(b.rchunks(usize::MAX)), vec![vec![3, 4, 5, 6, 7]]);
+            assert_eq!(
+                b.rchunks(2).flat_map(Iterator::rev).collect::<Vec<_>>(),
+                b.iter_rev().collect::<Vec<_>>()
+            );
+        }
+
+        test_rchunks(AllocRingBuffer::new(5));
+        test_rchunks(ConstGenericRingBuffer::<i32, 5>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.rchunks(2).map(|c| c.sum()).collect::<Vec<i32>>(), [5, 1]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_rchunks_zero_size() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.rchunks(0);
+    }
+
+    #[test]
+    fn run_test_downsample() {
+        fn test_downsample(mut b: impl RingBuffer<i32>) {
+            b.extend([0, 1, 2, 3, 4, 5, 6, 7]);
+            assert_eq!(b.to_vec(), vec![2, 3, 4, 5, 6, 7]);
+
+            let d = b.downsample(2);
+            assert_eq!(d.to_vec(), vec![2, 4, 6]);
+            assert_eq!(d.capacity(), 3);
+
+            let d = b.downsample(4);
+            assert_eq!(d.to_vec(), vec![2, 6]);
+            assert_eq!(d.capacity(), 2);
+
+            let d = b.downsample(1);
+            assert_eq!(d.to_vec(), b.to_vec());
+
+            let d = b.downsample(usize::MAX);
+            assert_eq!(d.to_vec(), vec![2]);
+            assert_eq!(d.capacity(), 1);
+
+            b.clear();
+            assert!(b.downsample(10).is_empty());
+        }
+
+        test_downsample(AllocRingBuffer::new(6));
+        test_downsample(ConstGenericRingBuffer::<i32, 6>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.downsample(2).to_vec(), vec![1, 3]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_downsample_zero_factor() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.downsample(0);
+    }
+
+    #[test]
+    fn run_test_upsample() {
+        fn test_upsample(mut b: impl RingBuffer<i32>) {
+            b.extend([1, 2, 3]);
+            assert_eq!(b.to_vec(), vec![2, 3]);
+
+            let u = b.upsample(2);
+            assert_eq!(u.to_vec(), vec![2, 0, 3, 0]);
+            assert_eq!(u.capacity(), 4);
+
+            let u = b.upsample(3);
+            assert_eq!(u.to_vec(), vec![2, 0, 0, 3, 0, 0]);
+
+            assert_eq!(b.upsample(1).to_vec(), b.to_vec());
+            // downsampling reverses upsampling
+            assert_eq!(b.upsample(4).downsample(4).to_vec(), b.to_vec());
+        }
+
+        test_upsample(AllocRingBuffer::new(2));
+        test_upsample(ConstGenericRingBuffer::<i32, 2>::new());
+
+        let b = GrowableAllocRingBuffer::from([1, 2]);
+        assert_eq!(b.upsample(2).to_vec(), vec![1, 0, 2, 0]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_upsample_zero_factor() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.upsample(0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Upsampled capacity overflows usize")]
+    fn test_upsample_capacity_overflow() {
+        let b = AllocRingBuffer::from([1, 2, 3]);
+        let _ = b.upsample(usize::MAX);
+    }
+
+    #[test]
+    fn run_test_autocorr() {
+        fn test_autocorr(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.autocorr(0), None);
+            b.extend([5, 5, 5]);
+            assert_eq!(b.autocorr(0), None);
+
+            // a signal with a period of 4
+            b.clear();
+            b.extend([0, 2, 0, -2, 0, 2, 0, -2, 0, 2, 0, -2]);
+            assert_eq!(b.autocorr(0), Some(1.0));
+            assert_eq!(b.autocorr(12), None);
+
+            let best_lag = (1..8)
+                .max_by(|&a, &c| b.autocorr(a).partial_cmp(&b.autocorr(c)).unwrap())
+                .unwrap();
+            assert_eq!(best_lag, 4);
+            assert_eq!(b.autocorr(2), Some(-10.0 / 12.0));
+        }
+
+        test_autocorr(AllocRingBuffer::new(12));
+        test_autocorr(GrowableAllocRingBuffer::with_capacity(12));
+        test_autocorr(ConstGenericRingBuffer::<i32, 12>::new());
+    }
+
+    #[test]
+    fn run_test_zero_crossings() {
+        fn test_zero_crossings(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.zero_crossings(), 0);
+            b.push(1);
+            assert_eq!(b.zero_crossings(), 0);
+
+            b.extend([-1, 1, -1]);
+            assert_eq!(b.zero_crossings(), 3);
+
+            b.extend([-2, 0, 3]);
+            assert_eq!(b.to_vec(), vec![-1, -2, 0, 3]);
+            // passing through zero is not a sign change between two adjacent elements
+            assert_eq!(b.zero_crossings(), 0);
+
+            b.extend([-3, 4]);
+            assert_eq!(b.to_vec(), vec![0, 3, -3, 4]);
+            assert_eq!(b.zero_crossings(), 2);
+        }
+
+        test_zero_crossings(AllocRingBuffer::new(4));
+        test_zero_crossings(ConstGenericRingBuffer::<i32, 4>::new());
+
+        let b = GrowableAllocRingBuffer::from([0.5, -0.5, -0.1, 0.2]);
+        assert_eq!(b.zero_crossings(), 2);
+    }
+
+    #[test]
+    fn run_test_rms() {
+        fn test_rms(mut b: impl RingBuffer<i32>) {
+            assert_eq!(b.rms(), None);
+
+            b.extend([1, -1, 1, -1]);
+            assert_eq!(b.rms(), Some(1.0));
+
+            b.extend([2, 2]);
+            assert_eq!(b.to_vec(), vec![1, -1, 2, 2]);
+            assert!((b.rms().unwrap() - 2.5f64.sqrt()).abs() < 1e-12);
+
+            b.clear();
+            b.push(0);
+            assert_eq!(b.rms(), Some(0.0));
+        }
+
+        test_rms(AllocRingBuffer::new(4));
+        test_rms(ConstGenericRingBuffer::<i32, 4>::new());
+
+        // a sine wave has an rms of its amplitude divided by sqrt(2)
+        let mut b = GrowableAllocRingBuffer::new();
+        b.extend((0..1000).map(|i| (f64::from(i) * core::f64::consts::PI / 500.0).sin() * 3.0));
+        let rms = b.rms().unwrap();
+        assert!((rms - 3.0 / core::f64::consts::SQRT_2).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_sqrt() {
+        use crate::ringbuffer_trait::sqrt;
+
+        for i in [0.0, 1e-300, 0.25, 1.0, 2.0, 3.0, 16.0, 1e10, 1.7e308] {
+            // newton's method may end up one ulp away from the correctly rounded result
+            assert!((sqrt(i) - i.sqrt()).abs() <= i.sqrt() * f64::EPSILON);
+        }
+        assert_eq!(sqrt(f64::INFINITY), f64::INFINITY);
+        assert!(sqrt(-1.0).is_nan());
+    }
+
+    #[test]
+    fn run_test_apply_gain_db() {
+        fn test_apply_gain_db(mut b: impl RingBuffer<f32>) {
+            b.extend([1.0, -0.5, 0.0, 0.25]);
+            b.apply_gain_db(6.0);
+            for (gained, original) in b.iter().zip([1.0, -0.5, 0.0, 0.25]) {
+                assert!((gained - original * 1.995_262).abs() < 1e-5);
+            }
+
+            b.apply_gain_db(-6.0);
+            for (gained, original) in b.iter().zip([1.0, -0.5, 0.0, 0.25]) {
+                assert!((gained - original).abs() < 1e-5);
+            }
+
+            b.apply_gain_db(-40.0);
+            assert!((b[0] - 0.01).abs() < 1e-7);
+        }
+
+        fn test_apply_gain_db_f64(mut b: impl RingBuffer<f64>) {
+            b.extend([3.0, 1.0, 2.0]);
+            b.apply_gain_db(20.0);
+            assert!(b
+                .iter()
+                .zip([10.0, 20.0])
+                .all(|(a, b)| (a - b).abs() < 1e-5));
+
+            b.apply_gain_db(f32::NEG_INFINITY);
+            assert!(b.iter().all(|&i| i == 0.0));
+        }
+
+        test_apply_gain_db(AllocRingBuffer::new(4));
+        test_apply_gain_db(GrowableAllocRingBuffer::with_capacity(4));
+        test_apply_gain_db(ConstGenericRingBuffer::<f32, 4>::new());
+
+        test_apply_gain_db_f64(AllocRingBuffer::new(2));
+        test_apply_gain_db_f64(ConstGenericRingBuffer::<f64, 2>::new());
+    }
+
+    #[test]
+    fn run_test_dedup() {
+        fn test_dedup(mut b: impl RingBuffer<i32>) {
+            b.dedup();
+            assert!(b.is_empty());
+
+            b.extend([1, 1, 1, 2, 2, 1, 3, 3]);
+            b.dedup();
+            assert_eq!(b.to_vec(), vec![1, 2, 1, 3]);
+
+            // compares against the last kept element, not the previous one
+            b.dedup_by(|a, b| *a <= *b);
+            assert_eq!(b.to_vec(), vec![1, 2, 3]);
+        }
+
+        test_dedup(AllocRingBuffer::new(8));
+        test_dedup(GrowableAllocRingBuffer::new());
+        test_dedup(ConstGenericRingBuffer::<i32, 8>::new());
+    }
+
+    #[test]
+    fn test_growable_get_mut_signed() {
+        let mut b = GrowableAllocRingBuffer::from([1, 2, 3]);
+        assert_eq!(b.back_mut(), Some(&mut 3));
+        assert_eq!(b.get_mut_signed(-2), Some(&mut 2));
+        assert_eq!(b.get_mut_signed(-4), Some(&mut 3));
+    }
+
+    #[test]
+    fn run_test_dedup_wrapped() {
+        fn test_dedup_wrapped(mut b: impl RingBuffer<i32>) {
+            b.extend([0, 0, 0, 1, 2, 2, 2, 2]);
+            // the run of twos straddles the end of the storage
+            assert_eq!(b.to_vec(), vec![0, 1, 2, 2, 2, 2]);
+
+            b.dedup();
+            assert_eq!(b.to_vec(), vec![0, 1, 2]);
+            b.extend([2, 3, 3]);
+            assert_eq!(b.to_vec(), vec![0, 1, 2, 2, 3, 3]);
+            b.dedup();
+            assert_eq!(b.to_vec(), vec![0, 1, 2, 3]);
+        }
+
+        test_dedup_wrapped(AllocRingBuffer::new(6));
+        test_dedup_wrapped(ConstGenericRingBuffer::<i32, 6>::new());
+    }
+
+    #[test]
+    fn run_test_dedup_drops() {
+        use std::rc::Rc;
+
+        fn test_dedup_drops(mut b: impl RingBuffer<Rc<()>>) {
+            let counter = Rc::new(());
+            for _ in 0..4 {
+                b.push(Rc::clone(&counter));
+            }
+
+            b.dedup_by(|a, b| Rc::ptr_eq(a, b));
+            assert_eq!(b.len(), 1);
+            assert_eq!(Rc::strong_count(&counter), 2);
+        }
+
+        test_dedup_drops(AllocRingBuffer::new(4));
+        test_dedup_drops(GrowableAllocRingBuffer::new());
+        test_dedup_drops(ConstGenericRingBuffer::<_, 4>::new());
+    }
+
+    #[test]
+    fn run_test_retain_newest_wrapped() {
+        fn test_retain_newest_wrapped(mut b: impl RingBuffer<i32>) {
+            b.extend(0..10);
+            assert_eq!(b.to_vec(), vec![6, 7, 8, 9]);
+            b.retain_newest(1, |&i| i < 9);
+            assert_eq!(b.to_vec(), vec![8]);
+        }
+
+        test_retain_newest_wrapped(AllocRingBuffer::new(4));
+        test_retain_newest_wrapped(ConstGenericRingBuffer::<i32, 4>::new());
+    }
+
+    #[test]
+    fn test_hash() {
+        use std::collections::HashSet;
+
+        fn test_hash<RB: RingBuffer<i32> + Hash + Eq>(mut a: RB, mut b: RB) {
+            a.extend([1, 2, 3, 4]);
+            // wrap b around, leaving the same contents at different positions
+            b.extend([0, 0, 0, 1, 2, 3, 4]);
+            assert!(a == b);
+
+            let mut set = HashSet::new();
+            assert!(set.insert(a));
+            assert!(!set.insert(b));
+            assert_eq!(set.len(), 1);
+        }
+
+        test_hash(AllocRingBuffer::new(4), AllocRingBuffer::new(4));
+        test_hash(
+            ConstGenericRingBuffer::<i32, 4>::new(),
+            ConstGenericRingBuffer::<i32, 4>::new(),
+        );
+
+        let mut a = GrowableAllocRingBuffer::with_capacity(4);
+        let mut b = GrowableAllocRingBuffer::with_capacity(4);
+        a.extend([1, 2, 3]);
+        b.extend([0, 1, 2, 3]);
+        b.skip();
+        let mut set = HashSet::new();
+        assert!(set.insert(a));
+        assert!(!set.insert(b));
+    }
+
+    #[test]
+    fn run_test_diff_positions() {
+        fn test_diff_positions(mut a: impl RingBuffer<i32>, mut b: impl RingBuffer<i32>) {
+            a.extend([1, 2, 3]);
+            b.extend([1, 9, 3]);
+            assert_eq!(a.diff_positions(&b), vec![1]);
+            assert_eq!(a.diff_positions(&a), vec![]);
+
+            // only the common prefix is compared
+            b.push(4);
+            assert_eq!(a.diff_positions(&b), vec![1]);
+            assert_eq!(b.diff_positions(&a), vec![1]);
+        }
+
+        test_diff_positions(AllocRingBuffer::new(4), AllocRingBuffer::new(4));
+        test_diff_positions(
+            ConstGenericRingBuffer::<i32, 4>::new(),
+            GrowableAllocRingBuffer::with_capacity(4),
+        );
+    }
 }
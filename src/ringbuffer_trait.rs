@@ -1,10 +1,17 @@
-use core::ops::{Index, IndexMut};
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::iter::{Cloned, Enumerate, Rev, StepBy, Sum};
+use core::marker::PhantomData;
+use core::ops::{Bound, Index, IndexMut, MulAssign, Range, RangeBounds};
 
 #[cfg(feature = "alloc")]
 extern crate alloc;
 #[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
+#[cfg(feature = "alloc")]
+use crate::AllocRingBuffer;
+
 /// `RingBuffer` is a trait defining the standard interface for all `RingBuffer`
 /// implementations ([`AllocRingBuffer`](crate::AllocRingBuffer), [`ConstGenericRingBuffer`](crate::ConstGenericRingBuffer))
 ///
@@ -56,6 +63,8 @@ pub unsafe trait RingBuffer<T>:
     }
 
     /// Returns the number of elements allocated for this ringbuffer (can be larger than capacity).
+    /// [`AllocRingBuffer`](crate::AllocRingBuffer) rounds its allocation up to the next power of
+    /// two, so this is the number to use when budgeting memory.
     fn buffer_size(&self) -> usize {
         // Safety: self is a RingBuffer
         unsafe { Self::ptr_buffer_size(self) }
@@ -75,7 +84,10 @@ pub unsafe trait RingBuffer<T>:
     #[doc(hidden)]
     unsafe fn ptr_buffer_size(rb: *const Self) -> usize;
 
-    /// Pushes a value onto the buffer. Cycles around if capacity is reached.
+    /// Pushes a value onto the buffer. When the buffer is full, the oldest element is
+    /// overwritten. Use [`try_push`](RingBuffer::try_push) to keep the buffer unchanged and get
+    /// the value back instead, or [`push_force_pop`](RingBuffer::push_force_pop) to get the
+    /// overwritten element.
     fn push(&mut self, value: T);
 
     /// alias for [`push`](RingBuffer::push), forming a more natural counterpart to [`dequeue`](RingBuffer::dequeue)
@@ -83,9 +95,231 @@ pub unsafe trait RingBuffer<T>:
         self.push(value);
     }
 
+    /// Pushes a value onto the buffer if it isn't full. Otherwise the buffer is left unchanged,
+    /// and the value is handed back in `Err`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(1);
+    /// assert_eq!(rb.try_push(1), Ok(()));
+    /// assert_eq!(rb.try_push(2), Err(2));
+    /// assert_eq!(rb.to_vec(), vec![1]);
+    /// ```
+    fn try_push(&mut self, value: T) -> Result<(), T> {
+        if self.is_full() {
+            Err(value)
+        } else {
+            self.push(value);
+            Ok(())
+        }
+    }
+
+    /// Pushes the items of `iter` until the ringbuffer is full. Unlike [`extend`](Extend::extend),
+    /// this never overwrites elements: the remaining items are consumed and dropped instead.
+    /// Returns a [`PushReport`] which summarizes how many items were pushed and rejected.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// let report = rb.push_all_reporting(0..10);
+    ///
+    /// assert_eq!(report.pushed, 4);
+    /// assert_eq!(report.rejected, 6);
+    /// assert_eq!(report.fill_ratio, 1.0);
+    /// assert_eq!(rb.to_vec(), vec![0, 1, 2, 3]);
+    /// ```
+    fn push_all_reporting<I: IntoIterator<Item = T>>(&mut self, iter: I) -> PushReport {
+        let mut pushed = 0;
+        let mut rejected = 0;
+        for i in iter {
+            if self.is_full() {
+                rejected += 1;
+            } else {
+                self.push(i);
+                pushed += 1;
+            }
+        }
+
+        PushReport::new(pushed, rejected, self.len(), self.capacity())
+    }
+
+    /// Pushes a value onto the buffer like [`push`](RingBuffer::push), but when the buffer is full,
+    /// moves the oldest element out and returns it instead of dropping it.
+    /// Returns `None` when there was free space.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// assert_eq!(rb.push_force_pop(1), None);
+    /// assert_eq!(rb.push_force_pop(2), None);
+    /// assert_eq!(rb.push_force_pop(3), Some(1));
+    /// assert_eq!(rb.to_vec(), vec![2, 3]);
+    /// ```
+    fn push_force_pop(&mut self, value: T) -> Option<T> {
+        let evicted = if self.is_full() { self.dequeue() } else { None };
+        self.push(value);
+        evicted
+    }
+
+    /// Pushes a value onto the buffer like [`push_force_pop`](RingBuffer::push_force_pop), but
+    /// returns a [`PushResult`] telling whether the value went into a free slot or overwrote
+    /// the oldest element, which is then moved out.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, PushResult, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(1);
+    /// assert_eq!(rb.push_overwriting(1), PushResult::Inserted);
+    /// assert_eq!(rb.push_overwriting(2), PushResult::Overwrote(1));
+    /// ```
+    fn push_overwriting(&mut self, value: T) -> PushResult<T> {
+        match self.push_force_pop(value) {
+            Some(evicted) => PushResult::Overwrote(evicted),
+            None => PushResult::Inserted,
+        }
+    }
+
+    /// Pushes a value onto the buffer like [`push`](RingBuffer::push), and returns whether an
+    /// element was lost because the buffer was full. Cheaper than
+    /// [`push_force_pop`](RingBuffer::push_force_pop) when you only need to count drops.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// assert!(!rb.saturating_push(1));
+    /// assert!(!rb.saturating_push(2));
+    /// assert!(rb.saturating_push(3));
+    /// assert_eq!(rb.to_vec(), vec![2, 3]);
+    /// ```
+    fn saturating_push(&mut self, value: T) -> bool {
+        let full = self.is_full();
+        self.push(value);
+        full
+    }
+
+    /// Pushes all items of `items` onto the buffer, like calling [`push`](RingBuffer::push) for
+    /// each of them: when the buffer is full, the oldest elements are overwritten. The ringbuffers
+    /// of this crate copy the items in at most two blocks, and never copy more than `capacity`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(3);
+    /// rb.extend_from_slice(&[1, 2]);
+    /// rb.extend_from_slice(&[3, 4]);
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    ///
+    /// rb.extend_from_slice(&[5, 6, 7, 8, 9]);
+    /// assert_eq!(rb.to_vec(), vec![7, 8, 9]);
+    /// ```
+    fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Copy,
+    {
+        for &i in items {
+            self.push(i);
+        }
+    }
+
+    /// Returns an iterator which lazily pushes the items of `iter` onto the buffer, like
+    /// [`push_force_pop`](RingBuffer::push_force_pop), and yields the elements evicted by those
+    /// pushes. Each call to `next` pushes items until one of them evicts an element, so nothing
+    /// is pushed until the iterator is driven, and dropping it early leaves the rest of `iter`
+    /// unpushed.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// let evicted: Vec<_> = rb.push_force_stream(1..=5).collect();
+    /// assert_eq!(evicted, vec![1, 2, 3]);
+    /// assert_eq!(rb.to_vec(), vec![4, 5]);
+    /// ```
+    fn push_force_stream<I: Iterator<Item = T>>(
+        &mut self,
+        iter: I,
+    ) -> RingBufferPushForceStream<'_, T, Self, I> {
+        RingBufferPushForceStream::new(self, iter)
+    }
+
+    /// Moves all elements of `other` onto this ringbuffer in dequeue order, leaving `other` empty.
+    /// The elements are pushed with [`push`](RingBuffer::push), so when `other` holds more elements
+    /// than fit, only the newest ones survive.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3]);
+    /// let mut burst = ConstGenericRingBuffer::<_, 2>::from([4, 5]);
+    /// rb.append(&mut burst);
+    ///
+    /// assert_eq!(rb.to_vec(), vec![3, 4, 5]);
+    /// assert!(burst.is_empty());
+    /// ```
+    fn append<RB: RingBuffer<T>>(&mut self, other: &mut RB) {
+        self.extend(other.drain());
+    }
+
     /// dequeues the top item off the ringbuffer, and moves this item out.
     fn dequeue(&mut self) -> Option<T>;
 
+    /// Dequeues items into `out`, starting at its first element, for as long as `pred` returns
+    /// true for the front item and there is room left in `out`. Returns the number of items moved.
+    /// The first item for which `pred` returns false is left in the ringbuffer.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 10, 3]);
+    /// let mut out = [0; 4];
+    /// assert_eq!(rb.pop_into_while(&mut out, |&i| i < 5), 2);
+    /// assert_eq!(out, [1, 2, 0, 0]);
+    /// assert_eq!(rb.to_vec(), vec![10, 3]);
+    /// ```
+    fn pop_into_while<P: FnMut(&T) -> bool>(&mut self, out: &mut [T], mut pred: P) -> usize {
+        let mut count = 0;
+        for slot in out.iter_mut() {
+            if !self.peek().map_or(false, &mut pred) {
+                break;
+            }
+
+            if let Some(i) = self.dequeue() {
+                *slot = i;
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// Copies up to `out.len()` elements into `out` in dequeue order, without removing them
+    /// from the ringbuffer. Returns the number of elements copied. The ringbuffers of this
+    /// crate copy in at most two blocks.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3]);
+    /// let mut out = [0; 2];
+    /// assert_eq!(rb.copy_to_slice(&mut out), 2);
+    /// assert_eq!(out, [1, 2]);
+    /// assert_eq!(rb.len(), 3);
+    /// ```
+    fn copy_to_slice(&self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let mut count = 0;
+        for (slot, &i) in out.iter_mut().zip(self.iter()) {
+            *slot = i;
+            count += 1;
+        }
+        count
+    }
+
     /// dequeues the top item off the queue, but does not return it. Instead it is dropped.
     /// If the ringbuffer is empty, this function is a nop.
     #[inline]
@@ -93,6 +327,28 @@ pub unsafe trait RingBuffer<T>:
         let _ = self.dequeue();
     }
 
+    /// Dequeues the `n` oldest items off the queue and drops them, or all of them if there are
+    /// fewer than `n`. Returns the number of items skipped.
+    ///
+    /// For types that don't need to be dropped, the concrete ringbuffers only move their read
+    /// position, which takes constant time.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// assert_eq!(rb.skip_n(3), 3);
+    /// assert_eq!(rb.to_vec(), vec![4]);
+    /// assert_eq!(rb.skip_n(3), 1);
+    /// ```
+    fn skip_n(&mut self, n: usize) -> usize {
+        let n = n.min(self.len());
+        for _ in 0..n {
+            self.skip();
+        }
+        n
+    }
+
     /// Returns an iterator over the elements in the ringbuffer,
     /// dequeueing elements as they are iterated over.
     ///
@@ -115,7 +371,7 @@ pub unsafe trait RingBuffer<T>:
     /// assert_eq!(rb.len(), 0);
     ///
     /// ```
-    fn drain(&mut self) -> RingBufferDrainingIterator<T, Self> {
+    fn drain(&mut self) -> RingBufferDrainingIterator<'_, T, Self> {
         RingBufferDrainingIterator::new(self)
     }
 
@@ -139,8 +395,104 @@ pub unsafe trait RingBuffer<T>:
     }
 
     /// Empties the buffer entirely. Sets the length to 0 but keeps the capacity allocated.
+    /// Every element still in the buffer is dropped exactly once.
     fn clear(&mut self);
 
+    /// Returns true when the elements occupy a single contiguous region of the underlying
+    /// storage, and false when they wrap around its end. An empty ringbuffer is contiguous.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4]);
+    /// assert!(rb.is_contiguous());
+    ///
+    /// rb.push(5);
+    /// assert!(!rb.is_contiguous());
+    /// ```
+    fn is_contiguous(&self) -> bool;
+
+    /// Returns the elements as two slices, oldest first. The second slice is only non-empty
+    /// when the elements wrap around the end of the underlying storage.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// assert_eq!(rb.as_slices(), (&[2, 3, 4][..], &[5][..]));
+    /// ```
+    fn as_slices(&self) -> (&[T], &[T]);
+
+    /// Returns the elements as two mutable slices, oldest first, like
+    /// [`as_slices`](RingBuffer::as_slices).
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// let (head, tail) = rb.as_mut_slices();
+    /// head[0] = 10;
+    /// tail[0] = 50;
+    /// assert_eq!(rb.to_vec(), vec![10, 3, 4, 50]);
+    /// ```
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]);
+
+    /// Folds every contiguous region of the ringbuffer into an accumulator, oldest region
+    /// first. `f` is called at most twice, which makes it possible to use slice operations
+    /// instead of going through the elements one at a time like [`iter`](RingBuffer::iter) does.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1u8, 2, 3, 4, 5]);
+    /// let sum = rb.fold_regions(0u32, |acc, region| {
+    ///     acc + region.iter().map(|&i| u32::from(i)).sum::<u32>()
+    /// });
+    /// assert_eq!(sum, 14);
+    /// ```
+    fn fold_regions<B, F: FnMut(B, &[T]) -> B>(&self, init: B, mut f: F) -> B {
+        let (head, tail) = self.as_slices();
+        let acc = f(init, head);
+        if tail.is_empty() {
+            acc
+        } else {
+            f(acc, tail)
+        }
+    }
+
+    /// Passes the elements to `f` in dequeue order, at most two contiguous regions at a time,
+    /// and then removes them from the ringbuffer. This is the `no_std` counterpart of draining
+    /// the ringbuffer into an `io::Write`: `f` is called with the oldest region first, and once
+    /// more with the second region if the elements wrap around the end of the underlying storage.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let mut out = Vec::new();
+    /// rb.flush_to(|region| out.extend_from_slice(region));
+    /// assert_eq!(out, [2, 3, 4, 5]);
+    /// assert!(rb.is_empty());
+    /// ```
+    fn flush_to<F: FnMut(&[T])>(&mut self, mut f: F)
+    where
+        T: Copy,
+    {
+        let (head, tail) = self.as_slices();
+        f(head);
+        if !tail.is_empty() {
+            f(tail);
+        }
+        // the elements are Copy, so this only moves the read position
+        let _ = self.skip_n(self.len());
+    }
+
     /// Gets a value relative to the current index. 0 is the next index to be written to with push.
     /// -1 and down are the last elements pushed and 0 and up are the items that were pushed the longest ago.
     fn get_signed(&self, index: isize) -> Option<&T>;
@@ -163,6 +515,85 @@ pub unsafe trait RingBuffer<T>:
         unsafe { Self::ptr_get_mut(self, index).map(|i| &mut *i) }
     }
 
+    /// Gets the element at `index`, relative to the front like [`get`](RingBuffer::get). Unlike
+    /// `get`, the index doesn't wrap around: an index at or beyond the length returns an
+    /// [`IndexError`] which tells why the lookup failed.
+    ///
+    /// # Errors
+    /// Returns [`IndexError::Empty`] if the ringbuffer is empty, and
+    /// [`IndexError::OutOfBounds`] if `index` is not smaller than the length.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, IndexError, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// assert_eq!(rb.try_get(0), Err(IndexError::Empty { index: 0 }));
+    ///
+    /// rb.extend([1, 2, 3]);
+    /// assert_eq!(rb.try_get(2), Ok(&3));
+    /// assert_eq!(rb.try_get(3), Err(IndexError::OutOfBounds { index: 3, len: 3 }));
+    /// ```
+    fn try_get(&self, index: usize) -> Result<&T, IndexError> {
+        IndexError::check(index, self.len())?;
+        self.get(index).ok_or(IndexError::OutOfBounds {
+            index,
+            len: self.len(),
+        })
+    }
+
+    /// Gets the element at `index` mutably, like [`try_get`](RingBuffer::try_get).
+    ///
+    /// # Errors
+    /// Returns [`IndexError::Empty`] if the ringbuffer is empty, and
+    /// [`IndexError::OutOfBounds`] if `index` is not smaller than the length.
+    fn try_get_mut(&mut self, index: usize) -> Result<&mut T, IndexError> {
+        let len = self.len();
+        IndexError::check(index, len)?;
+        self.get_mut(index)
+            .ok_or(IndexError::OutOfBounds { index, len })
+    }
+
+    /// Returns mutable references to the elements at all `indices` at once, relative to the
+    /// front like the index passed to [`get`](RingBuffer::get). Returns `None` if any index is
+    /// out of bounds, or if an index occurs more than once, since the references would alias.
+    /// Unlike `get_mut`, the indices don't wrap around. This mirrors the unstable
+    /// `slice::get_many_mut`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// if let Some([a, b]) = rb.get_many_mut([0, 3]) {
+    ///     core::mem::swap(a, b);
+    /// }
+    /// assert_eq!(rb.to_vec(), vec![4, 2, 3, 1]);
+    ///
+    /// assert_eq!(rb.get_many_mut([1, 1]), None);
+    /// assert_eq!(rb.get_many_mut([1, 4]), None);
+    /// ```
+    fn get_many_mut<const N: usize>(&mut self, indices: [usize; N]) -> Option<[&mut T; N]> {
+        let len = self.len();
+        for (i, index) in indices.iter().enumerate() {
+            if *index >= len || indices[..i].contains(index) {
+                return None;
+            }
+        }
+
+        // every element pointer is derived from `rb`, so that creating one doesn't invalidate
+        // the ones created before it
+        let rb: *mut Self = self;
+        let mut ptrs = [core::ptr::null_mut(); N];
+        for (ptr, index) in ptrs.iter_mut().zip(indices) {
+            // Safety: rb points to self, which is a RingBuffer
+            *ptr = unsafe { Self::ptr_get_mut(rb, index)? };
+        }
+
+        // Safety: the indices are distinct and smaller than the length, so they don't wrap
+        // around and refer to distinct initialized elements. The references can't alias, and
+        // they borrow self mutably for as long as they live.
+        Some(ptrs.map(|ptr| unsafe { &mut *ptr }))
+    }
+
     /// same as [`get_mut`](RingBuffer::get_mut) but on raw pointers.
     ///
     /// # Safety
@@ -194,121 +625,1532 @@ pub unsafe trait RingBuffer<T>:
         self.get(0)
     }
 
-    /// Returns a mutable reference to the value at the back of the queue.
-    /// This is the value that will be overwritten by the next push.
-    /// (alias of peek)
-    #[inline]
-    fn front_mut(&mut self) -> Option<&mut T> {
-        self.get_mut(0)
+    /// Returns a mutable reference to the value at the front of the queue.
+    /// This is the value that will be overwritten by the next push.
+    #[inline]
+    fn front_mut(&mut self) -> Option<&mut T> {
+        self.get_mut(0)
+    }
+
+    /// Returns the value at the back of the queue.
+    /// This is the item that was pushed most recently.
+    #[inline]
+    fn back(&self) -> Option<&T> {
+        self.get_signed(-1)
+    }
+
+    /// Returns a mutable reference to the value at the back of the queue.
+    /// This is the item that was pushed most recently.
+    #[inline]
+    fn back_mut(&mut self) -> Option<&mut T> {
+        self.get_mut_signed(-1)
+    }
+
+    /// Returns the oldest element, which is the next one to be dequeued. Like [`slice::first`],
+    /// this is the element at index 0. (alias of [`front`](RingBuffer::front))
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.extend([1, 2, 3]);
+    /// assert_eq!(rb.first(), Some(&2));
+    /// assert_eq!(rb.dequeue(), Some(2));
+    /// ```
+    #[inline]
+    fn first(&self) -> Option<&T> {
+        self.front()
+    }
+
+    /// Returns a mutable reference to the oldest element, which is the next one to be dequeued.
+    /// (alias of [`front_mut`](RingBuffer::front_mut))
+    #[inline]
+    fn first_mut(&mut self) -> Option<&mut T> {
+        self.front_mut()
+    }
+
+    /// Returns the newest element, which is the one pushed most recently. Like
+    /// [`slice::last`], this is the element at index `len - 1`.
+    /// (alias of [`back`](RingBuffer::back))
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.extend([1, 2, 3]);
+    /// assert_eq!(rb.last(), Some(&3));
+    /// ```
+    #[inline]
+    fn last(&self) -> Option<&T> {
+        self.back()
+    }
+
+    /// Returns a mutable reference to the newest element, which is the one pushed most recently.
+    /// (alias of [`back_mut`](RingBuffer::back_mut))
+    #[inline]
+    fn last_mut(&mut self) -> Option<&mut T> {
+        self.back_mut()
+    }
+
+    /// Gets a value relative to the back of the queue, counting towards the front: 0 is the
+    /// item that was pushed most recently (the same as [`back`](RingBuffer::back)), 1 is the
+    /// item pushed before that, and so on.
+    ///
+    /// For `index < len`, this is equivalent to `get(len - 1 - index)`. Unlike
+    /// [`get`](RingBuffer::get), the index doesn't wrap around: indices at or beyond the length
+    /// return `None`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3]);
+    /// assert_eq!(rb.get_from_back(0), Some(&3));
+    /// assert_eq!(rb.get_from_back(2), Some(&1));
+    /// assert_eq!(rb.get_from_back(3), None);
+    /// ```
+    #[inline]
+    fn get_from_back(&self, index: usize) -> Option<&T> {
+        let len = self.len();
+        if index < len {
+            self.get(len - 1 - index)
+        } else {
+            None
+        }
+    }
+
+    /// Gets a value relative to the back of the queue mutably, see
+    /// [`get_from_back`](RingBuffer::get_from_back).
+    #[inline]
+    fn get_from_back_mut(&mut self, index: usize) -> Option<&mut T> {
+        let len = self.len();
+        if index < len {
+            self.get_mut(len - 1 - index)
+        } else {
+            None
+        }
+    }
+
+    /// Creates a mutable iterator over the buffer starting from the item pushed the longest ago,
+    /// and ending at the element most recently pushed.
+    ///
+    /// The iterator borrows the ringbuffer mutably for as long as it is alive, so the ringbuffer
+    /// can't be read or modified in any other way while iterating.
+    #[inline]
+    fn iter_mut(&mut self) -> RingBufferMutIterator<'_, T, Self> {
+        RingBufferMutIterator::new(self)
+    }
+
+    /// Creates a mutable iterator over the buffer like [`iter_mut`](RingBuffer::iter_mut), which
+    /// also yields the index of each element. The index is relative to the front, so it is what
+    /// you'd pass to [`get_mut`](RingBuffer::get_mut) for the same element.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 1, 1]);
+    /// for (i, el) in rb.iter_mut_enumerated() {
+    ///     *el *= i + 1;
+    /// }
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+    /// ```
+    #[inline]
+    fn iter_mut_enumerated(&mut self) -> Enumerate<RingBufferMutIterator<'_, T, Self>> {
+        self.iter_mut().enumerate()
+    }
+
+    /// Creates an iterator over the buffer starting from the item pushed the longest ago,
+    /// and ending at the element most recently pushed.
+    ///
+    /// The iterator borrows the ringbuffer for as long as it is alive, so elements can't be
+    /// pushed or dequeued while iterating.
+    ///
+    /// ```compile_fail
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3]);
+    /// let mut iter = rb.iter();
+    /// rb.push(4);
+    /// let _ = iter.next();
+    /// ```
+    #[inline]
+    fn iter(&self) -> RingBufferIterator<'_, T, Self> {
+        RingBufferIterator::new(self)
+    }
+
+    /// Creates an iterator over clones of the elements, starting from the element most recently
+    /// pushed and ending at the item pushed the longest ago.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3]);
+    /// assert_eq!(rb.iter_cloned_rev().collect::<Vec<_>>(), vec![3, 2, 1]);
+    /// ```
+    #[inline]
+    fn iter_cloned_rev<'a>(&'a self) -> Cloned<Rev<RingBufferIterator<'a, T, Self>>>
+    where
+        T: Clone + 'a,
+    {
+        self.iter().rev().cloned()
+    }
+
+    /// Creates an iterator over the elements, starting from the element most recently pushed and
+    /// ending at the item pushed the longest ago. This is `iter().rev()`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3]);
+    /// assert_eq!(rb.iter_rev().collect::<Vec<_>>(), vec![&3, &2, &1]);
+    /// ```
+    #[inline]
+    fn iter_rev(&self) -> Rev<RingBufferIterator<'_, T, Self>> {
+        self.iter().rev()
+    }
+
+    /// Creates an iterator over every `step`th element, starting with the oldest one. This is
+    /// `iter().step_by(step)`, which skips over the elements in between without visiting them,
+    /// because the iterator jumps ahead within the contiguous regions of the storage.
+    ///
+    /// # Panics
+    /// Panics when `step` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5, 6, 7]);
+    /// assert_eq!(rb.stride(3).collect::<Vec<_>>(), vec![&1, &4, &7]);
+    /// ```
+    #[inline]
+    fn stride(&self, step: usize) -> StepBy<RingBufferIterator<'_, T, Self>> {
+        self.iter().step_by(step)
+    }
+
+    /// Creates an iterator over the elements in `range`, relative to the front like the index
+    /// passed to [`get`](RingBuffer::get). The elements may wrap around the end of the storage,
+    /// so this returns an iterator instead of a slice.
+    ///
+    /// # Panics
+    /// Panics if the start of the range is greater than its end, or if the end is greater than
+    /// the length of the ringbuffer, like slice indexing does.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5, 6]);
+    /// assert_eq!(rb.range(2..5).copied().collect::<Vec<_>>(), vec![3, 4, 5]);
+    /// assert_eq!(rb.range(4..).copied().collect::<Vec<_>>(), vec![5, 6]);
+    /// ```
+    fn range<R: RangeBounds<usize>>(&self, range: R) -> RingBufferIterator<'_, T, Self> {
+        let len = self.len();
+        let start = match range.start_bound() {
+            Bound::Included(&i) => i,
+            Bound::Excluded(&i) => i.checked_add(1).expect("range start out of bounds"),
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&i) => i.checked_add(1).expect("range end out of bounds"),
+            Bound::Excluded(&i) => i,
+            Bound::Unbounded => len,
+        };
+
+        assert!(
+            start <= end,
+            "range start index {} is greater than end index {}",
+            start,
+            end
+        );
+        assert!(
+            end <= len,
+            "range end index {} out of range for ringbuffer of length {}",
+            end,
+            len
+        );
+        RingBufferIterator::with_range(self, start, end)
+    }
+
+    /// Creates an iterator over the elements from index `start` to the back, like
+    /// `iter().skip(start)`, but without stepping over the skipped elements. If `start` is at
+    /// least [`len`](RingBuffer::len), the iterator is empty.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// assert_eq!(rb.iter_from(2).collect::<Vec<_>>(), vec![&3, &4]);
+    /// assert_eq!(rb.iter_from(10).next(), None);
+    /// ```
+    #[inline]
+    fn iter_from(&self, start: usize) -> RingBufferIterator<'_, T, Self> {
+        let len = self.len();
+        RingBufferIterator::with_range(self, start.min(len), len)
+    }
+
+    /// Returns an iterator over all overlapping windows of `size` consecutive elements,
+    /// like [`slice::windows`]. If the ringbuffer holds fewer than `size` elements,
+    /// no windows are returned.
+    ///
+    /// The elements of a ringbuffer may wrap around the end of its storage, so unlike
+    /// [`slice::windows`] every window is an iterator over references instead of a slice.
+    /// Creating a window is O(1), and no elements are moved or copied.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let sums: Vec<i32> = rb.windows(2).map(|w| w.sum()).collect();
+    /// assert_eq!(sums, vec![5, 7, 9]);
+    /// ```
+    fn windows(&self, size: usize) -> RingBufferWindows<'_, T, Self> {
+        assert_ne!(size, 0, "Window size must be greater than 0");
+        RingBufferWindows::new(self, size)
+    }
+
+    /// Calls `f` with every overlapping window of `size` consecutive elements, like
+    /// [`windows`](RingBuffer::windows). Each window is passed as a slice of references, which
+    /// is collected into a single scratch buffer that is reused for all windows.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// let mut sums = Vec::new();
+    /// rb.for_each_window(3, |w| sums.push(w.iter().copied().sum::<i32>()));
+    /// assert_eq!(sums, vec![6, 9]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn for_each_window<F: FnMut(&[&T])>(&self, size: usize, mut f: F) {
+        let mut scratch = Vec::with_capacity(size);
+        for window in self.windows(size) {
+            scratch.clear();
+            scratch.extend(window);
+            f(&scratch);
+        }
+    }
+
+    /// Returns an iterator over chunks of `size` consecutive elements, starting at the front,
+    /// like [`slice::chunks`]. The last chunk has fewer than `size` elements if `size` does not
+    /// divide the length of the ringbuffer.
+    ///
+    /// Like with [`windows`](RingBuffer::windows), every chunk is an iterator over references
+    /// instead of a slice, because the elements may wrap around the end of the storage.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5]);
+    /// let chunks: Vec<Vec<i32>> = rb.chunks(2).map(|c| c.copied().collect()).collect();
+    /// assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    /// ```
+    fn chunks(&self, size: usize) -> RingBufferChunks<'_, T, Self> {
+        assert_ne!(size, 0, "Chunk size must be greater than 0");
+        RingBufferChunks::new(self, size, false)
+    }
+
+    /// Returns an iterator over chunks of exactly `size` consecutive elements, starting at
+    /// the front, like [`slice::chunks_exact`]. If `size` does not divide the length of the
+    /// ringbuffer, the remaining elements at the back are not returned.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5]);
+    /// let chunks: Vec<Vec<i32>> = rb.chunks_exact(2).map(|c| c.copied().collect()).collect();
+    /// assert_eq!(chunks, vec![vec![1, 2], vec![3, 4]]);
+    /// ```
+    fn chunks_exact(&self, size: usize) -> RingBufferChunks<'_, T, Self> {
+        assert_ne!(size, 0, "Chunk size must be greater than 0");
+        RingBufferChunks::new(self, size, true)
+    }
+
+    /// Returns an iterator over chunks of `size` consecutive elements, starting at the back,
+    /// like [`slice::rchunks`]. The first chunk holds the `size` newest elements, and if `size`
+    /// does not divide the length of the ringbuffer, the last chunk holds the remaining oldest
+    /// elements. The elements within a chunk are in dequeue order; call `rev` on a chunk to get
+    /// them newest first.
+    ///
+    /// # Panics
+    /// Panics if `size` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5]);
+    /// let chunks: Vec<Vec<i32>> = rb.rchunks(2).map(|c| c.copied().collect()).collect();
+    /// assert_eq!(chunks, vec![vec![4, 5], vec![2, 3], vec![1]]);
+    /// ```
+    fn rchunks(&self, size: usize) -> RingBufferRChunks<'_, T, Self> {
+        assert_ne!(size, 0, "Chunk size must be greater than 0");
+        RingBufferRChunks::new(self, size)
+    }
+
+    /// Converts the buffer to a vector. This Copies all elements in the ringbuffer.
+    #[cfg(feature = "alloc")]
+    fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Copies the elements into a vector, in dequeue order. Gives the same result as
+    /// [`to_vec`](RingBuffer::to_vec), but copies the contiguous regions of the ringbuffer as a
+    /// whole instead of cloning one element at a time, which is a lot faster for large buffers.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// assert_eq!(rb.copy_into_vec(), vec![2, 3, 4, 5]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn copy_into_vec(&self) -> Vec<T>
+    where
+        T: Copy,
+    {
+        let (head, tail) = self.as_slices();
+        let mut res = Vec::with_capacity(head.len() + tail.len());
+        res.extend_from_slice(head);
+        res.extend_from_slice(tail);
+        res
+    }
+
+    /// Clones the `k` oldest elements into a vector, in dequeue order. If the ringbuffer holds
+    /// fewer than `k` elements, all of them are cloned.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5]);
+    /// assert_eq!(rb.oldest_n(3), vec![1, 2, 3]);
+    /// assert_eq!(rb.oldest_n(8), vec![1, 2, 3, 4, 5]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn oldest_n(&self, k: usize) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().take(k).cloned().collect()
+    }
+
+    /// Clones every `factor`-th element, starting with the front, into a new [`AllocRingBuffer`].
+    /// The capacity of the new ringbuffer is the capacity of this one divided by `factor`,
+    /// rounded up.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5, 6]);
+    /// let downsampled = rb.downsample(2);
+    /// assert_eq!(downsampled.to_vec(), vec![1, 3, 5]);
+    /// assert_eq!(downsampled.capacity(), 3);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn downsample(&self, factor: usize) -> AllocRingBuffer<T>
+    where
+        T: Clone,
+    {
+        assert_ne!(factor, 0, "Downsampling factor must be greater than 0");
+
+        let capacity = self.capacity() / factor + usize::from(self.capacity() % factor != 0);
+        let mut res = AllocRingBuffer::new(capacity.max(1));
+        res.extend(self.iter().step_by(factor).cloned());
+        res
+    }
+
+    /// Clones every element into a new [`AllocRingBuffer`], each followed by `factor - 1`
+    /// default values (zero stuffing). The capacity of the new ringbuffer is the capacity of
+    /// this one multiplied by `factor`.
+    ///
+    /// # Panics
+    /// Panics if `factor` is zero, or if the capacity multiplied by `factor` overflows `usize`
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2]);
+    /// let upsampled = rb.upsample(2);
+    /// assert_eq!(upsampled.to_vec(), vec![1, 0, 2, 0]);
+    /// assert_eq!(upsampled.capacity(), 4);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn upsample(&self, factor: usize) -> AllocRingBuffer<T>
+    where
+        T: Clone + Default,
+    {
+        assert_ne!(factor, 0, "Upsampling factor must be greater than 0");
+
+        let capacity = self
+            .capacity()
+            .max(1)
+            .checked_mul(factor)
+            .expect("Upsampled capacity overflows usize");
+        let mut res = AllocRingBuffer::new(capacity);
+        for i in self.iter() {
+            res.push(i.clone());
+            for _ in 1..factor {
+                res.push(T::default());
+            }
+        }
+        res
+    }
+
+    /// Returns the indices at which the elements of this ringbuffer and `other` differ.
+    /// Only the first `min(self.len(), other.len())` elements are compared.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let a = AllocRingBuffer::from([1, 2, 3]);
+    /// let b = AllocRingBuffer::from([1, 9, 3]);
+    /// assert_eq!(a.diff_positions(&b), vec![1]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    fn diff_positions<RB: RingBuffer<T>>(&self, other: &RB) -> Vec<usize>
+    where
+        T: PartialEq,
+    {
+        self.iter()
+            .zip(other.iter())
+            .enumerate()
+            .filter(|(_, (a, b))| a != b)
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Returns true if the elements of the ringbuffer, in dequeue order, are equal to `other`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.extend([1, 2, 3]);
+    /// assert!(rb.eq_slice(&[2, 3]));
+    /// assert!(!rb.eq_slice(&[1, 2, 3]));
+    /// ```
+    fn eq_slice(&self, other: &[T]) -> bool
+    where
+        T: PartialEq,
+    {
+        self.len() == other.len() && self.iter().zip(other).all(|(a, b)| a == b)
+    }
+
+    /// Returns true if elem is in the ringbuffer.
+    ///
+    /// The contiguous regions returned by [`as_slices`](RingBuffer::as_slices) are searched with
+    /// [`slice::contains`], which is much faster than comparing element by element for types
+    /// like `u8`.
+    fn contains(&self, elem: &T) -> bool
+    where
+        T: PartialEq,
+    {
+        let (head, tail) = self.as_slices();
+        head.contains(elem) || tail.contains(elem)
+    }
+
+    /// Returns the index of the first occurrence of `needle` as a run of consecutive elements,
+    /// or `None` if it doesn't occur. The index is relative to the front, like the index passed
+    /// to [`get`](RingBuffer::get). Occurrences which wrap around the end of the storage are
+    /// found as well. An empty `needle` is found at index 0.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend(*b"ab\r\nc");
+    /// assert_eq!(rb.contains_seq(b"\r\n"), Some(1));
+    /// assert_eq!(rb.contains_seq(b"\n\r"), None);
+    /// ```
+    fn contains_seq(&self, needle: &[T]) -> Option<usize>
+    where
+        T: PartialEq,
+    {
+        let len = self.len();
+        if needle.len() > len {
+            return None;
+        }
+
+        (0..=len - needle.len()).find(|&start| {
+            needle
+                .iter()
+                .enumerate()
+                .all(|(offset, i)| self.get(start + offset) == Some(i))
+        })
+    }
+
+    /// Returns the index of the first element (starting from the front) for which `pred`
+    /// returns true, or `None` if no element matches.
+    ///
+    /// The index is relative to the front of the ringbuffer, like the index passed to
+    /// [`get`](RingBuffer::get). It is only valid until the next push or dequeue.
+    fn position<P: FnMut(&T) -> bool>(&self, pred: P) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    /// Returns the index of the first element (starting from the front) for which `pred` returns
+    /// false, assuming the ringbuffer is partitioned: all elements for which `pred` returns true
+    /// come before all elements for which it returns false. Returns `len` if `pred` is true for
+    /// every element. Uses a binary search, so if the ringbuffer isn't partitioned, the result is
+    /// unspecified.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 10, 11]);
+    /// assert_eq!(rb.partition_point(|&i| i < 5), 3);
+    /// ```
+    fn partition_point<P: FnMut(&T) -> bool>(&self, mut pred: P) -> usize {
+        let mut low = 0;
+        let mut high = self.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if self.get(mid).map_or(false, &mut pred) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+
+    /// Binary searches the ringbuffer for `x`, like [`slice::binary_search`]. Returns `Ok` with
+    /// the index of a matching element, or `Err` with the index where `x` could be inserted while
+    /// keeping the ringbuffer sorted. If several elements match, any one of them may be returned.
+    ///
+    /// The indices are relative to the front of the ringbuffer, like the index passed to
+    /// [`get`](RingBuffer::get). The elements must be sorted in dequeue order, otherwise the
+    /// result is meaningless.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 5, 8]);
+    /// assert_eq!(rb.binary_search(&5), Ok(2));
+    /// assert_eq!(rb.binary_search(&4), Err(2));
+    /// ```
+    fn binary_search(&self, x: &T) -> Result<usize, usize>
+    where
+        T: Ord,
+    {
+        self.binary_search_by(|i| i.cmp(x))
+    }
+
+    /// Binary searches the ringbuffer with a comparator function, like
+    /// [`slice::binary_search_by`]. `f` returns whether an element is less than, equal to or
+    /// greater than the target. See [`binary_search`](RingBuffer::binary_search).
+    fn binary_search_by<F: FnMut(&T) -> Ordering>(&self, mut f: F) -> Result<usize, usize> {
+        let mut low = 0;
+        let mut high = self.len();
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match self.get(mid).map(&mut f) {
+                Some(Ordering::Less) => low = mid + 1,
+                Some(Ordering::Greater) => high = mid,
+                _ => return Ok(mid),
+            }
+        }
+        Err(low)
+    }
+
+    /// Binary searches the ringbuffer for an element whose key, as returned by `f`, is `key`.
+    /// See [`binary_search`](RingBuffer::binary_search).
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([(10, 'a'), (20, 'b'), (30, 'c')]);
+    /// assert_eq!(rb.binary_search_by_key(&20, |&(t, _)| t), Ok(1));
+    /// ```
+    fn binary_search_by_key<B: Ord, F: FnMut(&T) -> B>(
+        &self,
+        key: &B,
+        mut f: F,
+    ) -> Result<usize, usize> {
+        self.binary_search_by(|i| f(i).cmp(key))
+    }
+
+    /// Returns a reference to the first element (starting from the front) for which `pred`
+    /// returns true, or `None` if no element matches.
+    fn find<P: FnMut(&T) -> bool>(&self, mut pred: P) -> Option<&T> {
+        self.iter().find(|i| pred(i))
+    }
+
+    /// Keeps at most `max` of the most recently pushed elements for which `pred` returns true,
+    /// and drops all other elements. The order of the remaining elements is preserved.
+    ///
+    /// `pred` is called exactly once for every element, in dequeue order.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4, 5, 6]);
+    /// rb.retain_newest(2, |i| i % 2 == 0);
+    /// assert_eq!(rb.to_vec(), vec![4, 6]);
+    /// ```
+    fn retain_newest<F: FnMut(&T) -> bool>(&mut self, max: usize, pred: F) {
+        retain_newest_by_requeue(self, max, pred);
+    }
+
+    /// Keeps only the elements for which `f` returns true, and drops the others. The order of
+    /// the remaining elements is preserved.
+    ///
+    /// `f` is called exactly once for every element, in dequeue order.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4, 5, 6]);
+    /// rb.retain(|i| i % 2 == 0);
+    /// assert_eq!(rb.to_vec(), vec![2, 4, 6]);
+    /// ```
+    fn retain<F: FnMut(&T) -> bool>(&mut self, mut f: F) {
+        self.retain_mut(|i| f(i));
+    }
+
+    /// Keeps only the elements for which `f` returns true, like [`retain`](RingBuffer::retain),
+    /// but passes a mutable reference so that `f` can also modify the elements it keeps.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// // decrement the time to live of every entry, and drop the expired ones
+    /// let mut rb = AllocRingBuffer::from([('a', 1), ('b', 3), ('c', 2)]);
+    /// rb.retain_mut(|(_, ttl)| {
+    ///     *ttl -= 1;
+    ///     *ttl > 0
+    /// });
+    /// assert_eq!(rb.to_vec(), vec![('b', 2), ('c', 1)]);
+    /// ```
+    fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+        retain_mut_by_requeue(self, f);
+    }
+
+    /// Copies the elements in the range `src` to the elements starting at `dest`, like
+    /// [`slice::copy_within`]. Both ranges are relative to the front, like the index passed to
+    /// [`get`](RingBuffer::get), and may overlap.
+    ///
+    /// # Panics
+    /// Panics if `src` is not a valid range within the ringbuffer, or if `dest + src.len()` is
+    /// greater than the length of the ringbuffer.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// rb.copy_within(0..2, 1);
+    /// assert_eq!(rb.to_vec(), vec![1, 1, 2, 4]);
+    /// ```
+    fn copy_within(&mut self, src: Range<usize>, dest: usize)
+    where
+        T: Copy,
+    {
+        let len = self.len();
+        assert!(
+            src.start <= src.end && src.end <= len,
+            "source range out of bounds"
+        );
+        let count = src.end - src.start;
+        assert!(dest <= len - count, "destination out of bounds");
+
+        let mut copy = |offset: usize| {
+            if let Some(&value) = self.get(src.start + offset) {
+                if let Some(target) = self.get_mut(dest + offset) {
+                    *target = value;
+                }
+            }
+        };
+
+        // copy in the direction which doesn't overwrite elements before they are read
+        if dest <= src.start {
+            (0..count).for_each(&mut copy);
+        } else {
+            (0..count).rev().for_each(&mut copy);
+        }
+    }
+
+    /// Inserts `value` at `index`, relative to the front like the index passed to
+    /// [`get`](RingBuffer::get). The elements at `index` and after it move one position
+    /// towards the back to make room. This takes O(len - index) time.
+    ///
+    /// If the ringbuffer is full, nothing is overwritten, and `value` is handed back
+    /// in the `Err` variant instead.
+    ///
+    /// # Panics
+    /// Panics if `index` is greater than the length of the ringbuffer
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3]);
+    /// assert_eq!(rb.insert(1, 10), Ok(()));
+    /// assert_eq!(rb.to_vec(), vec![1, 10, 2, 3]);
+    /// assert_eq!(rb.insert(0, 20), Err(20));
+    /// ```
+    fn insert(&mut self, index: usize, value: T) -> Result<(), T> {
+        let len = self.len();
+        assert!(index <= len, "index out of bounds");
+        if self.is_full() {
+            return Err(value);
+        }
+
+        self.push(value);
+        // move the new element from the back to its index. Both pointers are derived from `rb`,
+        // so that creating the second one doesn't invalidate the first.
+        let rb: *mut Self = self;
+        for i in (index..len).rev() {
+            // Safety: both indices are smaller than the length, and distinct
+            unsafe {
+                if let (Some(a), Some(b)) = (Self::ptr_get_mut(rb, i), Self::ptr_get_mut(rb, i + 1))
+                {
+                    core::ptr::swap(a, b);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Removes and returns the element at `index`, relative to the front like the index
+    /// passed to [`get`](RingBuffer::get), or returns `None` if `index` is out of bounds.
+    /// The elements in front of it move one position towards the back to close the gap,
+    /// so `remove(0)` is the same as [`dequeue`](RingBuffer::dequeue). This takes O(index) time.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// assert_eq!(rb.remove(2), Some(3));
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 4]);
+    /// assert_eq!(rb.remove(3), None);
+    /// ```
+    fn remove(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        // move the element from its index to the front
+        let rb: *mut Self = self;
+        for i in (0..index).rev() {
+            // Safety: both indices are smaller than the length, and distinct
+            unsafe {
+                if let (Some(a), Some(b)) = (Self::ptr_get_mut(rb, i), Self::ptr_get_mut(rb, i + 1))
+                {
+                    core::ptr::swap(a, b);
+                }
+            }
+        }
+
+        self.dequeue()
+    }
+
+    /// Removes and returns the element at `index`, relative to the front like the index
+    /// passed to [`get`](RingBuffer::get), or returns `None` if `index` is out of bounds.
+    /// The gap is filled with the front element, so this takes O(1) time but does not preserve
+    /// the order of the elements. This mirrors [`VecDeque::swap_remove_front`].
+    ///
+    /// [`VecDeque::swap_remove_front`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html#method.swap_remove_front
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// assert_eq!(rb.swap_remove_front(2), Some(3));
+    /// assert_eq!(rb.to_vec(), vec![2, 1, 4]);
+    /// ```
+    fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        if index >= self.len() {
+            return None;
+        }
+
+        if index != 0 {
+            let rb: *mut Self = self;
+            // Safety: both indices are smaller than the length, and distinct
+            unsafe {
+                if let (Some(a), Some(b)) = (Self::ptr_get_mut(rb, 0), Self::ptr_get_mut(rb, index))
+                {
+                    core::ptr::swap(a, b);
+                }
+            }
+        }
+
+        self.dequeue()
+    }
+
+    /// Removes and returns the element at `index`, relative to the front like the index
+    /// passed to [`get`](RingBuffer::get), or returns `None` if `index` is out of bounds.
+    /// The gap is filled with the back element, the one most recently pushed, so this does not
+    /// preserve the order of the elements. This mirrors [`VecDeque::swap_remove_back`].
+    ///
+    /// The ringbuffers of this crate do this in O(1) time, the default implementation takes
+    /// O(len) time to remove the back element.
+    ///
+    /// [`VecDeque::swap_remove_back`]: https://doc.rust-lang.org/std/collections/struct.VecDeque.html#method.swap_remove_back
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// assert_eq!(rb.swap_remove_back(1), Some(2));
+    /// assert_eq!(rb.to_vec(), vec![1, 4, 3]);
+    /// ```
+    fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        let len = self.len();
+        if index >= len {
+            return None;
+        }
+
+        if index != len - 1 {
+            let rb: *mut Self = self;
+            // Safety: both indices are smaller than the length, and distinct
+            unsafe {
+                if let (Some(a), Some(b)) =
+                    (Self::ptr_get_mut(rb, index), Self::ptr_get_mut(rb, len - 1))
+                {
+                    core::ptr::swap(a, b);
+                }
+            }
+        }
+
+        self.remove(len - 1)
+    }
+
+    /// Shortens the ringbuffer to `len` elements, keeping the `len` most recently pushed
+    /// elements and dropping the oldest ones.
+    /// If `len` is greater than or equal to the current length, this function is a nop.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// rb.truncate(1);
+    /// assert_eq!(rb.to_vec(), vec![4]);
+    /// ```
+    fn truncate(&mut self, len: usize) {
+        let _ = self.skip_n(self.len().saturating_sub(len));
+    }
+
+    /// Shortens the ringbuffer to `len` elements, keeping the `len` oldest elements
+    /// and dropping the most recently pushed ones.
+    /// If `len` is greater than or equal to the current length, this function is a nop.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// rb.truncate_front(1);
+    /// assert_eq!(rb.to_vec(), vec![1]);
+    /// ```
+    fn truncate_front(&mut self, len: usize) {
+        let remove = self.len().saturating_sub(len);
+        if remove == 0 {
+            return;
+        }
+
+        // move the elements we keep to the back, then drop the ones in front of them
+        for _ in 0..len {
+            if let Some(i) = self.dequeue() {
+                self.push(i);
+            }
+        }
+        for _ in 0..remove {
+            self.skip();
+        }
+    }
+
+    /// Removes consecutive repeated elements, like [`Vec::dedup`](alloc::vec::Vec::dedup).
+    /// Removed elements are dropped, and the order of the remaining elements is preserved.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 1, 2, 3, 3, 3, 1]);
+    /// rb.dedup();
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3, 1]);
+    /// ```
+    fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.dedup_by(|a, b| a == b);
+    }
+
+    /// Removes all but the first of consecutive elements for which `same_bucket` returns true,
+    /// like [`Vec::dedup_by`](alloc::vec::Vec::dedup_by). `same_bucket` is called with the
+    /// element in question first, and the last element that was kept second.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(["foo", "Foo", "bar", "baz"]);
+    /// rb.dedup_by(|a, b| a.eq_ignore_ascii_case(b));
+    /// assert_eq!(rb.to_vec(), vec!["foo", "bar", "baz"]);
+    /// ```
+    fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, same_bucket: F) {
+        dedup_by_requeue(self, same_bucket);
+    }
+
+    /// Rotates the ringbuffer `n` places to the left, such that the element at index `n`
+    /// becomes the front. `n` is taken modulo the length of the buffer.
+    /// If the ringbuffer is empty, this function is a nop.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// rb.rotate_left(1);
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4, 1]);
+    /// ```
+    fn rotate_left(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        for _ in 0..n % self.len() {
+            if let Some(i) = self.dequeue() {
+                self.push(i);
+            }
+        }
+    }
+
+    /// Rotates the ringbuffer to the left until the front element matches `pred`, and returns
+    /// true. If no element matches, the ringbuffer is left unchanged and false is returned.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+    /// assert!(rb.rotate_until(|&i| i > 2));
+    /// assert_eq!(rb.to_vec(), vec![3, 4, 1, 2]);
+    /// assert!(!rb.rotate_until(|&i| i > 4));
+    /// assert_eq!(rb.to_vec(), vec![3, 4, 1, 2]);
+    /// ```
+    fn rotate_until<P: FnMut(&T) -> bool>(&mut self, pred: P) -> bool {
+        match self.position(pred) {
+            Some(index) => {
+                self.rotate_left(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Rotates the ringbuffer `n` places to the right, such that the element at index `len - n`
+    /// becomes the front. `n` is taken modulo the length of the buffer.
+    /// If the ringbuffer is empty, this function is a nop.
+    fn rotate_right(&mut self, n: usize) {
+        if self.is_empty() {
+            return;
+        }
+
+        let len = self.len();
+        self.rotate_left(len - n % len);
+    }
+
+    /// Returns the number of elements outside of the inclusive range `[min, max]`.
+    /// Elements which can't be compared to the bounds, like `NaN`, are counted as well.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([0.5, 1.0, 1.5, -2.0]);
+    /// assert_eq!(rb.clip_count(-1.0, 1.0), 2);
+    /// ```
+    fn clip_count(&self, min: T, max: T) -> usize
+    where
+        T: PartialOrd,
+    {
+        self.iter().filter(|&i| !(min <= *i && *i <= max)).count()
+    }
+
+    /// Returns the sum of all elements, or the zero value of `T` if the ringbuffer is empty.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3]);
+    /// assert_eq!(rb.sum(), 6);
+    /// ```
+    fn sum(&self) -> T
+    where
+        T: for<'a> Sum<&'a T>,
+    {
+        self.iter().sum()
+    }
+
+    /// Returns the arithmetic mean of the elements, or `None` if the ringbuffer is empty.
+    /// The elements are accumulated as `f64`, so the sum can't overflow.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([u32::MAX, u32::MAX]);
+    /// assert_eq!(rb.mean(), Some(f64::from(u32::MAX)));
+    /// ```
+    fn mean(&self) -> Option<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let sum: f64 = self.iter().map(|&i| i.into()).sum();
+        Some(sum / self.len() as f64)
+    }
+
+    /// Returns a reference to the smallest element, or `None` if the ringbuffer is empty.
+    /// If several elements are equally small, the first one (starting from the front) is returned.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb: AllocRingBuffer<i32> = AllocRingBuffer::from([3, -1, 2]);
+    /// assert_eq!(rb.min_element(), Some(&-1));
+    /// ```
+    fn min_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().min()
+    }
+
+    /// Returns a reference to the largest element, or `None` if the ringbuffer is empty.
+    /// If several elements are equally large, the last one (starting from the front) is returned.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb: AllocRingBuffer<i32> = AllocRingBuffer::from([3, -1, 2]);
+    /// assert_eq!(rb.max_element(), Some(&3));
+    /// ```
+    fn max_element(&self) -> Option<&T>
+    where
+        T: Ord,
+    {
+        self.iter().max()
+    }
+
+    /// Returns the root mean square of the elements, `sqrt(mean(x^2))`,
+    /// or `None` if the ringbuffer is empty.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([3.0, -3.0, 3.0, -3.0]);
+    /// assert_eq!(rb.rms(), Some(3.0));
+    /// ```
+    fn rms(&self) -> Option<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        if self.is_empty() {
+            return None;
+        }
+
+        let sum_of_squares: f64 = self
+            .iter()
+            .map(|&i| {
+                let i = i.into();
+                i * i
+            })
+            .sum();
+        Some(sqrt(sum_of_squares / self.len() as f64))
+    }
+
+    /// Returns the autocorrelation of the elements at `lag`, normalized such that the
+    /// autocorrelation at lag zero is 1. Values close to 1 indicate the contents repeat
+    /// with a period of `lag` elements.
+    ///
+    /// Returns `None` if `lag` is not smaller than the length of the ringbuffer, or if all elements
+    /// are equal, in which case the autocorrelation is undefined.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, -1, 1, -1]);
+    /// assert_eq!(rb.autocorr(0), Some(1.0));
+    /// assert_eq!(rb.autocorr(1), Some(-0.75));
+    /// assert_eq!(rb.autocorr(4), None);
+    /// ```
+    fn autocorr(&self, lag: usize) -> Option<f64>
+    where
+        T: Copy + Into<f64>,
+    {
+        if lag >= self.len() {
+            return None;
+        }
+
+        let mean = self.mean()?;
+        let variance: f64 = self
+            .iter()
+            .map(|&i| {
+                let d = i.into() - mean;
+                d * d
+            })
+            .sum();
+        if variance == 0.0 {
+            return None;
+        }
+
+        let covariance: f64 = self
+            .iter()
+            .zip(self.iter().skip(lag))
+            .map(|(&a, &b)| (a.into() - mean) * (b.into() - mean))
+            .sum();
+        Some(covariance / variance)
+    }
+
+    /// Returns the number of adjacent pairs of elements whose signs differ, using `T::default()`
+    /// as zero. Elements equal to zero have no sign, so pairs containing them are not counted.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, -1, 1, -1]);
+    /// assert_eq!(rb.zero_crossings(), 3);
+    /// ```
+    fn zero_crossings(&self) -> usize
+    where
+        T: PartialOrd + Default,
+    {
+        let zero = T::default();
+        self.iter()
+            .zip(self.iter().skip(1))
+            .filter(|(a, b)| (**a < zero && **b > zero) || (**a > zero && **b < zero))
+            .count()
+    }
+
+    /// Multiplies every element by the linear gain corresponding to `db` decibels,
+    /// `10^(db / 20)`. A gain of +6 dB roughly doubles every element, -6 dB roughly halves it.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([0.5f32, -1.0]);
+    /// rb.apply_gain_db(20.0);
+    /// assert_eq!(rb.to_vec(), vec![5.0, -10.0]);
+    /// ```
+    fn apply_gain_db(&mut self, db: f32)
+    where
+        T: Copy + MulAssign + From<f32>,
+    {
+        let gain = T::from(db_to_gain(db));
+        let (head, tail) = self.as_mut_slices();
+        head.iter_mut().chain(tail).for_each(|i| *i *= gain);
+    }
+}
+
+mod iter {
+    use crate::RingBuffer;
+    use core::iter::FusedIterator;
+    use core::marker::PhantomData;
+    use core::ptr::NonNull;
+    use core::slice;
+
+    /// `RingBufferIterator` iterates over the elements of a `RingBuffer`. It splits the elements
+    /// into their two contiguous regions once, using [`RingBuffer::as_slices`], and then walks
+    /// those like a slice iterator instead of masking the index of every element.
+    pub struct RingBufferIterator<'rb, T, RB: RingBuffer<T>> {
+        head: slice::Iter<'rb, T>,
+        tail: slice::Iter<'rb, T>,
+        phantom: PhantomData<&'rb RB>,
+    }
+
+    impl<'rb, T, RB: RingBuffer<T>> RingBufferIterator<'rb, T, RB> {
+        #[inline]
+        pub fn new(obj: &'rb RB) -> Self {
+            Self::with_range(obj, 0, obj.len())
+        }
+
+        /// Creates an iterator over the elements at index `start` up to (but excluding) `end`.
+        #[inline]
+        pub(crate) fn with_range(obj: &'rb RB, start: usize, end: usize) -> Self {
+            debug_assert!(start <= end && end <= obj.len());
+            let (head, tail) = obj.as_slices();
+            let (head, tail) = if start < head.len() {
+                (
+                    &head[start..end.min(head.len())],
+                    &tail[..end.saturating_sub(head.len())],
+                )
+            } else {
+                (&tail[start - head.len()..end - head.len()], &[][..])
+            };
+            Self {
+                head: head.iter(),
+                tail: tail.iter(),
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> Iterator for RingBufferIterator<'rb, T, RB> {
+        type Item = &'rb T;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            match self.head.next() {
+                Some(i) => Some(i),
+                None => self.tail.next(),
+            }
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let remaining = self.head.len() + self.tail.len();
+            (remaining, Some(remaining))
+        }
+
+        #[inline]
+        fn nth(&mut self, n: usize) -> Option<Self::Item> {
+            let head_len = self.head.len();
+            if n < head_len {
+                self.head.nth(n)
+            } else {
+                self.head = [].iter();
+                self.tail.nth(n - head_len)
+            }
+        }
+
+        #[inline]
+        fn fold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, mut f: F) -> B {
+            let acc = self.head.fold(init, &mut f);
+            self.tail.fold(acc, f)
+        }
+    }
+
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> FusedIterator for RingBufferIterator<'rb, T, RB> {}
+
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> ExactSizeIterator for RingBufferIterator<'rb, T, RB> {}
+
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> DoubleEndedIterator for RingBufferIterator<'rb, T, RB> {
+        #[inline]
+        fn next_back(&mut self) -> Option<Self::Item> {
+            match self.tail.next_back() {
+                Some(i) => Some(i),
+                None => self.head.next_back(),
+            }
+        }
+
+        #[inline]
+        fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+            let tail_len = self.tail.len();
+            if n < tail_len {
+                self.tail.nth_back(n)
+            } else {
+                self.tail = [].iter();
+                self.head.nth_back(n - tail_len)
+            }
+        }
+
+        #[inline]
+        fn rfold<B, F: FnMut(B, Self::Item) -> B>(self, init: B, mut f: F) -> B {
+            let acc = self.tail.rfold(init, &mut f);
+            self.head.rfold(acc, f)
+        }
     }
 
-    /// Returns the value at the back of the queue.
-    /// This is the item that was pushed most recently.
-    #[inline]
-    fn back(&self) -> Option<&T> {
-        self.get_signed(-1)
+    /// `RingBufferWindows` iterates over overlapping windows of a `RingBuffer`, see
+    /// [`RingBuffer::windows`]. `index` is the start of the next window.
+    pub struct RingBufferWindows<'rb, T, RB: RingBuffer<T>> {
+        obj: &'rb RB,
+        size: usize,
+        index: usize,
+        phantom: PhantomData<T>,
     }
 
-    /// Returns a mutable reference to the value at the back of the queue.
-    /// This is the item that was pushed most recently.
-    #[inline]
-    fn back_mut(&mut self) -> Option<&mut T> {
-        self.get_mut_signed(-1)
+    impl<'rb, T, RB: RingBuffer<T>> RingBufferWindows<'rb, T, RB> {
+        #[inline]
+        pub fn new(obj: &'rb RB, size: usize) -> Self {
+            Self {
+                obj,
+                size,
+                index: 0,
+                phantom: PhantomData,
+            }
+        }
     }
 
-    /// Creates a mutable iterator over the buffer starting from the item pushed the longest ago,
-    /// and ending at the element most recently pushed.
-    #[inline]
-    fn iter_mut(&mut self) -> RingBufferMutIterator<T, Self> {
-        RingBufferMutIterator::new(self)
-    }
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> Iterator for RingBufferWindows<'rb, T, RB> {
+        type Item = RingBufferIterator<'rb, T, RB>;
 
-    /// Creates an iterator over the buffer starting from the item pushed the longest ago,
-    /// and ending at the element most recently pushed.
-    #[inline]
-    fn iter(&self) -> RingBufferIterator<T, Self> {
-        RingBufferIterator::new(self)
-    }
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            let end = self.index + self.size;
+            if end <= self.obj.len() {
+                let res = RingBufferIterator::with_range(self.obj, self.index, end);
+                self.index += 1;
+                Some(res)
+            } else {
+                None
+            }
+        }
 
-    /// Converts the buffer to a vector. This Copies all elements in the ringbuffer.
-    #[cfg(feature = "alloc")]
-    fn to_vec(&self) -> Vec<T>
-    where
-        T: Clone,
-    {
-        self.iter().cloned().collect()
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            let remaining = (self.obj.len() + 1).saturating_sub(self.index + self.size);
+            (remaining, Some(remaining))
+        }
     }
 
-    /// Returns true if elem is in the ringbuffer.
-    fn contains(&self, elem: &T) -> bool
-    where
-        T: PartialEq,
-    {
-        self.iter().any(|i| i == elem)
-    }
-}
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> FusedIterator for RingBufferWindows<'rb, T, RB> {}
 
-mod iter {
-    use crate::RingBuffer;
-    use core::iter::FusedIterator;
-    use core::marker::PhantomData;
-    use core::ptr::NonNull;
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> ExactSizeIterator for RingBufferWindows<'rb, T, RB> {}
 
-    /// `RingBufferIterator` holds a reference to a `RingBuffer` and iterates over it. `index` is the
-    /// current iterator position.
-    pub struct RingBufferIterator<'rb, T, RB: RingBuffer<T>> {
+    /// `RingBufferChunks` iterates over non-overlapping chunks of a `RingBuffer`, see
+    /// [`RingBuffer::chunks`] and [`RingBuffer::chunks_exact`]. `index` is the start of the
+    /// next chunk, and `end` is the index after the last element which may be part of a chunk.
+    pub struct RingBufferChunks<'rb, T, RB: RingBuffer<T>> {
         obj: &'rb RB,
-        len: usize,
+        size: usize,
         index: usize,
+        end: usize,
         phantom: PhantomData<T>,
     }
 
-    impl<'rb, T, RB: RingBuffer<T>> RingBufferIterator<'rb, T, RB> {
+    impl<'rb, T, RB: RingBuffer<T>> RingBufferChunks<'rb, T, RB> {
         #[inline]
-        pub fn new(obj: &'rb RB) -> Self {
+        pub fn new(obj: &'rb RB, size: usize, exact: bool) -> Self {
+            let len = obj.len();
             Self {
                 obj,
-                len: obj.len(),
+                size,
                 index: 0,
+                end: if exact { len - len % size } else { len },
                 phantom: PhantomData,
             }
         }
     }
 
-    impl<'rb, T: 'rb, RB: RingBuffer<T>> Iterator for RingBufferIterator<'rb, T, RB> {
-        type Item = &'rb T;
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> Iterator for RingBufferChunks<'rb, T, RB> {
+        type Item = RingBufferIterator<'rb, T, RB>;
 
         #[inline]
         fn next(&mut self) -> Option<Self::Item> {
-            if self.index < self.len {
-                let res = self.obj.get(self.index);
-                self.index += 1;
-                res
+            if self.index < self.end {
+                let end = self.end.min(self.index + self.size);
+                let res = RingBufferIterator::with_range(self.obj, self.index, end);
+                self.index = end;
+                Some(res)
             } else {
                 None
             }
         }
 
         fn size_hint(&self) -> (usize, Option<usize>) {
-            (self.len, Some(self.len))
+            // `end` leaves out the partial chunk of `chunks_exact`, so this counts it only for
+            // `chunks`. Rounding up by adding `size - 1` could overflow.
+            let n = self.end - self.index;
+            let remaining = n / self.size + usize::from(n % self.size != 0);
+            (remaining, Some(remaining))
         }
     }
 
-    impl<'rb, T: 'rb, RB: RingBuffer<T>> FusedIterator for RingBufferIterator<'rb, T, RB> {}
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> FusedIterator for RingBufferChunks<'rb, T, RB> {}
 
-    impl<'rb, T: 'rb, RB: RingBuffer<T>> ExactSizeIterator for RingBufferIterator<'rb, T, RB> {}
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> ExactSizeIterator for RingBufferChunks<'rb, T, RB> {}
 
-    impl<'rb, T: 'rb, RB: RingBuffer<T>> DoubleEndedIterator for RingBufferIterator<'rb, T, RB> {
+    /// `RingBufferRChunks` iterates over non-overlapping chunks of a `RingBuffer`, starting at
+    /// the back, see [`RingBuffer::rchunks`]. `end` is the index after the last element of the
+    /// next chunk.
+    pub struct RingBufferRChunks<'rb, T, RB: RingBuffer<T>> {
+        obj: &'rb RB,
+        size: usize,
+        end: usize,
+        phantom: PhantomData<T>,
+    }
+
+    impl<'rb, T, RB: RingBuffer<T>> RingBufferRChunks<'rb, T, RB> {
         #[inline]
-        fn next_back(&mut self) -> Option<Self::Item> {
-            if self.len > 0 && self.index < self.len {
-                let res = self.obj.get(self.len - 1);
-                self.len -= 1;
-                res
+        pub fn new(obj: &'rb RB, size: usize) -> Self {
+            Self {
+                obj,
+                size,
+                end: obj.len(),
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> Iterator for RingBufferRChunks<'rb, T, RB> {
+        type Item = RingBufferIterator<'rb, T, RB>;
+
+        #[inline]
+        fn next(&mut self) -> Option<Self::Item> {
+            if self.end > 0 {
+                let start = self.end.saturating_sub(self.size);
+                let res = RingBufferIterator::with_range(self.obj, start, self.end);
+                self.end = start;
+                Some(res)
             } else {
                 None
             }
         }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            // rounding up by adding `size - 1` could overflow
+            let remaining = self.end / self.size + usize::from(self.end % self.size != 0);
+            (remaining, Some(remaining))
+        }
+    }
+
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> FusedIterator for RingBufferRChunks<'rb, T, RB> {}
+
+    impl<'rb, T: 'rb, RB: RingBuffer<T>> ExactSizeIterator for RingBufferRChunks<'rb, T, RB> {}
+
+    /// `RingBufferPushForceStream` pushes the items of `iter` onto a `RingBuffer` as it is
+    /// iterated over, and yields the elements they evict, see [`RingBuffer::push_force_stream`].
+    pub struct RingBufferPushForceStream<'rb, T, RB: RingBuffer<T>, I> {
+        obj: &'rb mut RB,
+        iter: I,
+        phantom: PhantomData<T>,
+    }
+
+    impl<'rb, T, RB: RingBuffer<T>, I> RingBufferPushForceStream<'rb, T, RB, I> {
+        #[inline]
+        pub fn new(obj: &'rb mut RB, iter: I) -> Self {
+            Self {
+                obj,
+                iter,
+                phantom: PhantomData,
+            }
+        }
+    }
+
+    impl<'rb, T, RB: RingBuffer<T>, I: Iterator<Item = T>> Iterator
+        for RingBufferPushForceStream<'rb, T, RB, I>
+    {
+        type Item = T;
+
+        fn next(&mut self) -> Option<T> {
+            for i in self.iter.by_ref() {
+                if let Some(evicted) = self.obj.push_force_pop(i) {
+                    return Some(evicted);
+                }
+            }
+            None
+        }
+
+        fn size_hint(&self) -> (usize, Option<usize>) {
+            (0, self.iter.size_hint().1)
+        }
     }
 
     /// `RingBufferMutIterator` holds a reference to a `RingBuffer` and iterates over it. `index` is the
@@ -431,9 +2273,263 @@ mod iter {
 }
 
 pub use iter::{
-    RingBufferDrainingIterator, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+    RingBufferChunks, RingBufferDrainingIterator, RingBufferIntoIterator, RingBufferIterator,
+    RingBufferMutIterator, RingBufferPushForceStream, RingBufferRChunks, RingBufferWindows,
 };
 
+/// Summarizes a batch of pushes, see [`RingBuffer::push_all_reporting`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PushReport {
+    /// The number of items which were pushed onto the ringbuffer.
+    pub pushed: usize,
+    /// The number of items which were dropped because the ringbuffer was full.
+    pub rejected: usize,
+    /// The length of the ringbuffer divided by its capacity after the batch,
+    /// between 0 (empty) and 1 (full).
+    pub fill_ratio: f32,
+}
+
+impl PushReport {
+    pub(crate) fn new(pushed: usize, rejected: usize, len: usize, capacity: usize) -> Self {
+        Self {
+            pushed,
+            rejected,
+            fill_ratio: if capacity == 0 {
+                0.0
+            } else {
+                len as f32 / capacity as f32
+            },
+        }
+    }
+}
+
+/// The outcome of [`RingBuffer::push_overwriting`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PushResult<T> {
+    /// The value was pushed into a free slot.
+    Inserted,
+    /// The ringbuffer was full, so pushing the value overwrote the oldest element, which is
+    /// moved out.
+    Overwrote(T),
+}
+
+/// The error returned by the fallible constructors of the ringbuffers, such as
+/// [`AllocRingBuffer::try_new`](crate::AllocRingBuffer::try_new), and by
+/// [`AllocRingBuffer::try_reserve`](crate::AllocRingBuffer::try_reserve).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum RingBufferError {
+    /// The requested capacity is zero. A fixed size ringbuffer must be able to hold at least
+    /// one element.
+    InvalidCapacity,
+    /// The requested capacity is too large to allocate, because its size in bytes overflows.
+    CapacityOverflow,
+    /// The allocator failed to allocate the memory for the ringbuffer.
+    AllocationFailed,
+}
+
+impl fmt::Display for RingBufferError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            RingBufferError::InvalidCapacity => write!(f, "Capacity must be greater than 0"),
+            RingBufferError::CapacityOverflow => write!(f, "Capacity overflow"),
+            RingBufferError::AllocationFailed => write!(f, "Memory allocation failed"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for RingBufferError {}
+
+/// The error returned by [`RingBuffer::try_get`] and [`RingBuffer::try_get_mut`] when there
+/// is no element at the requested index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IndexError {
+    /// The ringbuffer is empty, so there is no valid index.
+    Empty {
+        /// The requested index.
+        index: usize,
+    },
+    /// The index is not smaller than the length of the ringbuffer.
+    OutOfBounds {
+        /// The requested index.
+        index: usize,
+        /// The length of the ringbuffer.
+        len: usize,
+    },
+}
+
+impl IndexError {
+    fn check(index: usize, len: usize) -> Result<(), Self> {
+        if len == 0 {
+            Err(Self::Empty { index })
+        } else if index >= len {
+            Err(Self::OutOfBounds { index, len })
+        } else {
+            Ok(())
+        }
+    }
+}
+
+impl fmt::Display for IndexError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            IndexError::Empty { index } => {
+                write!(
+                    f,
+                    "index {} is out of bounds, the ringbuffer is empty",
+                    index
+                )
+            }
+            IndexError::OutOfBounds { index, len } => {
+                write!(f, "index {} is out of bounds for length {}", index, len)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IndexError {}
+
+/// The maximum number of elements printed by the `Debug` implementations of the ringbuffers.
+/// Longer ringbuffers print the first and last half of this many elements, separated by `..`.
+pub(crate) const DEBUG_ELEMENT_LIMIT: usize = 16;
+
+/// Used internally. Formats the contents of a ringbuffer as a list in dequeue order,
+/// eliding the middle when there are more than [`DEBUG_ELEMENT_LIMIT`] elements.
+pub(crate) struct DebugContents<'rb, T, RB: RingBuffer<T>>(&'rb RB, PhantomData<T>);
+
+impl<'rb, T, RB: RingBuffer<T>> DebugContents<'rb, T, RB> {
+    pub(crate) fn new(rb: &'rb RB) -> Self {
+        Self(rb, PhantomData)
+    }
+}
+
+impl<'rb, T: Debug, RB: RingBuffer<T>> Debug for DebugContents<'rb, T, RB> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let len = self.0.len();
+        if len <= DEBUG_ELEMENT_LIMIT {
+            f.debug_list().entries(self.0.iter()).finish()
+        } else {
+            let half = DEBUG_ELEMENT_LIMIT / 2;
+            f.debug_list()
+                .entries(self.0.iter().take(half))
+                .entry(&format_args!(".."))
+                .entries(self.0.iter().skip(len - half))
+                .finish()
+        }
+    }
+}
+
+/// Used internally. Keeps the elements for which `f` returns true by dequeueing every element
+/// and pushing the kept ones again. Ringbuffers which count their pushes call this from their own
+/// [`retain_mut`](RingBuffer::retain_mut), so that they can leave these re-pushes out.
+pub(crate) fn retain_mut_by_requeue<T, RB, F>(rb: &mut RB, mut f: F)
+where
+    RB: RingBuffer<T>,
+    F: FnMut(&mut T) -> bool,
+{
+    for _ in 0..rb.len() {
+        if let Some(mut i) = rb.dequeue() {
+            if f(&mut i) {
+                rb.push(i);
+            }
+        }
+    }
+}
+
+/// Used internally. The requeueing algorithm of [`retain_newest`](RingBuffer::retain_newest),
+/// see [`retain_mut_by_requeue`].
+pub(crate) fn retain_newest_by_requeue<T, RB, F>(rb: &mut RB, max: usize, mut pred: F)
+where
+    RB: RingBuffer<T>,
+    F: FnMut(&T) -> bool,
+{
+    retain_mut_by_requeue(rb, |i| pred(i));
+
+    for _ in 0..rb.len().saturating_sub(max) {
+        rb.skip();
+    }
+}
+
+/// Used internally. The requeueing algorithm of [`dedup_by`](RingBuffer::dedup_by),
+/// see [`retain_mut_by_requeue`].
+pub(crate) fn dedup_by_requeue<T, RB, F>(rb: &mut RB, mut same_bucket: F)
+where
+    RB: RingBuffer<T>,
+    F: FnMut(&mut T, &mut T) -> bool,
+{
+    let mut kept = 0;
+    for _ in 0..rb.len() {
+        if let Some(mut i) = rb.dequeue() {
+            let duplicate = kept > 0
+                && rb
+                    .back_mut()
+                    .map_or(false, |previous| same_bucket(&mut i, previous));
+
+            if !duplicate {
+                rb.push(i);
+                kept += 1;
+            }
+        }
+    }
+}
+
+/// Used internally. Computes the square root of a non-negative number.
+///
+/// `sqrt` is not available in `core`, so this refines an initial estimate, taken from halving
+/// the exponent of `x`, with Newton's method until it converges.
+pub(crate) fn sqrt(x: f64) -> f64 {
+    if x.is_nan() || x < 0.0 {
+        return f64::NAN;
+    } else if x == 0.0 || x.is_infinite() {
+        return x;
+    }
+
+    let mut estimate = f64::from_bits((x.to_bits() >> 1) + (1023 << 51));
+    loop {
+        let next = (estimate + x / estimate) / 2.0;
+        // newton's method converges from above, so stop as soon as it doesn't decrease anymore
+        if next >= estimate {
+            return estimate;
+        }
+        estimate = next;
+    }
+}
+
+/// Used internally. Converts a gain in decibels to a linear factor, `10^(db / 20)`.
+///
+/// `powf` is not available in `core`, so this computes `e^(db / 20 * ln(10))` by splitting off
+/// a power of two, and approximating the exponential of the small remainder with a Taylor series.
+pub(crate) fn db_to_gain(db: f32) -> f32 {
+    use core::f64::consts::{LN_10, LN_2};
+
+    let x = f64::from(db) / 20.0 * LN_10;
+    // outside of this range the result doesn't fit in an f32 anyway
+    if x.is_nan() {
+        return f32::NAN;
+    } else if x > 89.0 {
+        return f32::INFINITY;
+    } else if x < -104.0 {
+        return 0.0;
+    }
+
+    // x = k * ln(2) + r, with |r| <= ln(2) / 2
+    let k = (x / LN_2 + if x < 0.0 { -0.5 } else { 0.5 }) as i64;
+    let r = x - k as f64 * LN_2;
+
+    let mut term = 1.0;
+    let mut sum = 1.0;
+    for n in 1..20 {
+        term *= r / f64::from(n);
+        sum += term;
+    }
+
+    // 2^k, built directly from the exponent bits. k is well within the range of normal f64s.
+    let scale = f64::from_bits(((k + 1023) as u64) << 52);
+    (sum * scale) as f32
+}
+
 /// Implement various functions on implementors of [`RingBuffer`].
 /// This is to avoid duplicate code.
 macro_rules! impl_ringbuffer {
@@ -448,7 +2544,7 @@ macro_rules! impl_ringbuffer {
 /// Implement various functions on implementors of [`RingBuffer`].
 /// This is to avoid duplicate code.
 macro_rules! impl_ringbuffer_ext {
-    ($get_unchecked: ident, $get_unchecked_mut: ident, $readptr: ident, $writeptr: ident, $mask: expr) => {
+    ($get_unchecked: ident, $get_unchecked_mut: ident, $readptr: ident, $writeptr: ident, $total_pushed: ident, $mask: expr) => {
         #[inline]
         fn get_signed(&self, index: isize) -> Option<&T> {
             use core::ops::Not;
@@ -530,5 +2626,140 @@ macro_rules! impl_ringbuffer_ext {
             self.$readptr = 0;
             self.$writeptr = 0;
         }
+
+        #[inline]
+        fn is_contiguous(&self) -> bool {
+            let start = $mask(self.buffer_size(), self.$readptr);
+            start + self.len() <= self.buffer_size()
+        }
+
+        fn skip_n(&mut self, n: usize) -> usize {
+            let n = n.min(self.len());
+            if core::mem::needs_drop::<T>() {
+                for _ in 0..n {
+                    let _ = self.dequeue();
+                }
+            } else {
+                // nothing has to be dropped, so the elements can be left in the buffer
+                self.$readptr += n;
+            }
+            n
+        }
+
+        fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+            if index >= self.len() {
+                return None;
+            }
+
+            self.$writeptr -= 1;
+            let back = $mask(self.buffer_size(), self.$writeptr);
+
+            // SAFETY: the slot before the writeptr holds the newest element, which is
+            // initialized. The writeptr has already been moved, so it won't be read again.
+            let value = unsafe { core::ptr::read($get_unchecked_mut(self, back)) };
+            if index == self.len() {
+                return Some(value);
+            }
+
+            let index = $mask(self.buffer_size(), self.$readptr + index);
+            // SAFETY: index is smaller than the length, so the slot is initialized
+            Some(core::mem::replace(
+                unsafe { &mut *$get_unchecked_mut(self, index) },
+                value,
+            ))
+        }
+
+        fn truncate_front(&mut self, len: usize) {
+            while self.len() > len {
+                self.$writeptr -= 1;
+                let index = $mask(self.buffer_size(), self.$writeptr);
+
+                // SAFETY: the slot before the writeptr holds the newest element, which is
+                // initialized. The writeptr has already been moved, so it won't be read again.
+                unsafe {
+                    core::ptr::drop_in_place($get_unchecked_mut(self, index));
+                }
+            }
+        }
+
+        fn rotate_left(&mut self, n: usize) {
+            if self.is_empty() {
+                return;
+            }
+
+            let n = n % self.len();
+            if self.len() == self.buffer_size() {
+                // every slot of the buffer is initialized, so moving both pointers
+                // rotates the contents without moving any data.
+                self.$readptr += n;
+                self.$writeptr += n;
+            } else {
+                // the elements are pushed again, which doesn't count as pushing them
+                let total_pushed = self.$total_pushed;
+                for _ in 0..n {
+                    if let Some(i) = self.dequeue() {
+                        self.push(i);
+                    }
+                }
+                self.$total_pushed = total_pushed;
+            }
+        }
+
+        fn retain_mut<F: FnMut(&mut T) -> bool>(&mut self, f: F) {
+            let total_pushed = self.$total_pushed;
+            crate::ringbuffer_trait::retain_mut_by_requeue(self, f);
+            self.$total_pushed = total_pushed;
+        }
+
+        fn retain_newest<F: FnMut(&T) -> bool>(&mut self, max: usize, pred: F) {
+            let total_pushed = self.$total_pushed;
+            crate::ringbuffer_trait::retain_newest_by_requeue(self, max, pred);
+            self.$total_pushed = total_pushed;
+        }
+
+        fn dedup_by<F: FnMut(&mut T, &mut T) -> bool>(&mut self, same_bucket: F) {
+            let total_pushed = self.$total_pushed;
+            crate::ringbuffer_trait::dedup_by_requeue(self, same_bucket);
+            self.$total_pushed = total_pushed;
+        }
+    };
+}
+
+/// Implement `PartialEq` against slices, arrays, `Vec` and `VecDeque` for a ringbuffer type,
+/// comparing the elements in dequeue order.
+/// This is to avoid duplicate code.
+macro_rules! impl_partial_eq_sequences {
+    ($ty: ty, $($generics: tt)*) => {
+        impl<T: PartialEq, $($generics)*> PartialEq<[T]> for $ty {
+            fn eq(&self, other: &[T]) -> bool {
+                self.eq_slice(other)
+            }
+        }
+
+        impl<'a, T: PartialEq, $($generics)*> PartialEq<&'a [T]> for $ty {
+            fn eq(&self, other: &&'a [T]) -> bool {
+                self.eq_slice(other)
+            }
+        }
+
+        impl<T: PartialEq, const N: usize, $($generics)*> PartialEq<[T; N]> for $ty {
+            fn eq(&self, other: &[T; N]) -> bool {
+                self.eq_slice(other)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T: PartialEq, $($generics)*> PartialEq<alloc::vec::Vec<T>> for $ty {
+            fn eq(&self, other: &alloc::vec::Vec<T>) -> bool {
+                self.eq_slice(other)
+            }
+        }
+
+        #[cfg(feature = "alloc")]
+        impl<T: PartialEq, $($generics)*> PartialEq<alloc::collections::VecDeque<T>> for $ty {
+            fn eq(&self, other: &alloc::collections::VecDeque<T>) -> bool {
+                self.len() == other.len() && self.iter().eq(other)
+            }
+        }
     };
 }
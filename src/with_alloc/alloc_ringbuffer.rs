@@ -1,13 +1,20 @@
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
+use core::marker::PhantomData;
 use core::ops::{Index, IndexMut};
 
 use crate::ringbuffer_trait::{
-    RingBuffer, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+    DebugContents, PushResult, RingBuffer, RingBufferIntoIterator, RingBufferIterator,
+    RingBufferMutIterator,
 };
 
 extern crate alloc;
 
 // We need boxes, so depend on alloc
-use crate::{mask_and, GrowableAllocRingBuffer};
+use crate::{mask_and, GrowableAllocRingBuffer, RingBufferError};
+#[cfg(feature = "sync")]
+use crate::{Consumer, Producer, SpscRingBuffer};
 use core::ptr;
 
 /// The `AllocRingBuffer` is a `RingBuffer` which is based on a Vec. This means it allocates at runtime
@@ -36,7 +43,6 @@ use core::ptr;
 /// buffer.push(1);
 /// assert_eq!(buffer.to_vec(), vec![42, 1]);
 /// ```
-#[derive(Debug)]
 pub struct AllocRingBuffer<T> {
     buf: *mut T,
 
@@ -48,6 +54,16 @@ pub struct AllocRingBuffer<T> {
 
     readptr: usize,
     writeptr: usize,
+
+    // number of elements overwritten by push since the last reset
+    overwrite_count: u64,
+    // number of elements pushed since the buffer was created
+    total_pushed: u64,
+    // what push does when the buffer is full
+    policy: OverwritePolicy,
+    // fill level at which high_water_reached is set by a push, if any
+    high_water: Option<usize>,
+    high_water_reached: bool,
 }
 
 // SAFETY: all methods that require mutable access take &mut,
@@ -107,6 +123,9 @@ impl<T> From<alloc::vec::Vec<T>> for AllocRingBuffer<T> {
     }
 }
 
+/// Moves the elements of a `VecDeque` into a full `AllocRingBuffer`, keeping their order: the
+/// front of the deque becomes the oldest element. The capacity is the length of the deque, so
+/// this panics when the deque is empty.
 impl<T> From<alloc::collections::VecDeque<T>> for AllocRingBuffer<T> {
     fn from(value: alloc::collections::VecDeque<T>) -> Self {
         let mut res = AllocRingBuffer::new(value.len());
@@ -147,25 +166,77 @@ impl<T, const CAP: usize> From<crate::ConstGenericRingBuffer<T, CAP>> for AllocR
     }
 }
 
+/// Moves the elements of an `AllocRingBuffer` into a `VecDeque`, keeping their order: the oldest
+/// element becomes the front of the deque.
+impl<T> From<AllocRingBuffer<T>> for alloc::collections::VecDeque<T> {
+    fn from(value: AllocRingBuffer<T>) -> Self {
+        value.into_vec().into()
+    }
+}
+
+/// Clones the elements of an `AllocRingBuffer` into a `VecDeque`, in dequeue order.
+impl<T: Clone> From<&AllocRingBuffer<T>> for alloc::collections::VecDeque<T> {
+    fn from(value: &AllocRingBuffer<T>) -> Self {
+        value.iter().cloned().collect()
+    }
+}
+
 impl<T> Drop for AllocRingBuffer<T> {
     fn drop(&mut self) {
         self.drain().for_each(drop);
 
         let layout = alloc::alloc::Layout::array::<T>(self.size).unwrap();
-        unsafe {
-            alloc::alloc::dealloc(self.buf as *mut u8, layout);
+        // nothing was allocated for zero sized layouts, see try_new
+        if layout.size() != 0 {
+            unsafe {
+                alloc::alloc::dealloc(self.buf as *mut u8, layout);
+            }
         }
     }
 }
 
+impl<T: Debug> Debug for AllocRingBuffer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AllocRingBuffer")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .field("data", &DebugContents::new(self))
+            .finish()
+    }
+}
+
 impl<T: Clone> Clone for AllocRingBuffer<T> {
     fn clone(&self) -> Self {
         debug_assert_ne!(self.capacity, 0);
 
         let mut new = Self::new(self.capacity);
         self.iter().cloned().for_each(|i| new.push(i));
+        new.overwrite_count = self.overwrite_count;
+        new.total_pushed = self.total_pushed;
+        new.policy = self.policy;
+        new.high_water = self.high_water;
+        new.high_water_reached = self.high_water_reached;
         new
     }
+
+    /// Clones the contents of `source` into `self`. Unlike `*self = source.clone()`, this
+    /// reuses the existing allocation of `self` if it is large enough to hold `source.capacity()`
+    /// elements, and only reallocates otherwise.
+    fn clone_from(&mut self, source: &Self) {
+        if self.size < source.capacity {
+            *self = source.clone();
+            return;
+        }
+
+        self.clear();
+        self.capacity = source.capacity;
+        self.extend(source.iter().cloned());
+        self.overwrite_count = source.overwrite_count;
+        self.total_pushed = source.total_pushed;
+        self.policy = source.policy;
+        self.high_water = source.high_water;
+        self.high_water_reached = source.high_water_reached;
+    }
 }
 
 impl<T: PartialEq> PartialEq for AllocRingBuffer<T> {
@@ -178,6 +249,34 @@ impl<T: PartialEq> PartialEq for AllocRingBuffer<T> {
 
 impl<T: Eq + PartialEq> Eq for AllocRingBuffer<T> {}
 
+impl_partial_eq_sequences!(AllocRingBuffer<T>,);
+
+/// Compares the elements lexicographically, from the front. Buffers with equal elements are
+/// ordered by capacity, to be consistent with [`PartialEq`].
+impl<T: PartialOrd> PartialOrd for AllocRingBuffer<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match self.iter().partial_cmp(other.iter()) {
+            Some(Ordering::Equal) => Some(self.capacity.cmp(&other.capacity)),
+            ord => ord,
+        }
+    }
+}
+
+impl<T: Ord> Ord for AllocRingBuffer<T> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter()
+            .cmp(other.iter())
+            .then(self.capacity.cmp(&other.capacity))
+    }
+}
+
+impl<T: Hash> Hash for AllocRingBuffer<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|i| i.hash(state));
+    }
+}
+
 impl<T> IntoIterator for AllocRingBuffer<T> {
     type Item = T;
     type IntoIter = RingBufferIntoIterator<T, Self>;
@@ -230,6 +329,7 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
 
     #[inline]
     fn push(&mut self, value: T) {
+        let len = self.len();
         if self.is_full() {
             // mask with and is allowed here because size is always a power of two
             let previous_value =
@@ -244,6 +344,7 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
             }
 
             self.readptr += 1;
+            self.overwrite_count += 1;
         }
 
         // mask with and is allowed here because size is always a power of two
@@ -254,6 +355,92 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
         }
 
         self.writeptr += 1;
+        self.total_pushed += 1;
+        self.update_high_water(len);
+    }
+
+    fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Copy,
+    {
+        let len = self.len();
+        let overwritten = (self.len() + items.len()).saturating_sub(self.capacity);
+        self.total_pushed += items.len() as u64;
+        self.overwrite_count += overwritten as u64;
+
+        // all but the last `capacity` items would be overwritten anyway
+        let skipped = items.len().saturating_sub(self.capacity);
+        let items = &items[skipped..];
+
+        // mask with and is allowed here because size is always a power of two
+        let start = mask_and(self.size, self.writeptr);
+        let first_len = items.len().min(self.size - start);
+
+        // SAFETY: both blocks lie within the allocation of `size` elements, and at most
+        // `capacity <= size` slots are written. T is Copy, so the overwritten elements
+        // don't need to be dropped.
+        unsafe {
+            ptr::copy_nonoverlapping(items.as_ptr(), self.buf.add(start), first_len);
+            ptr::copy_nonoverlapping(
+                items.as_ptr().add(first_len),
+                self.buf,
+                items.len() - first_len,
+            );
+        }
+
+        self.writeptr += items.len();
+        self.readptr += overwritten - skipped;
+        self.update_high_water(len);
+    }
+
+    fn copy_to_slice(&self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = out.len().min(self.len());
+
+        // mask with and is allowed here because size is always a power of two
+        let start = mask_and(self.size, self.readptr);
+        let first_len = count.min(self.size - start);
+
+        // SAFETY: the first `count` elements from the readptr are initialized, the second
+        // block only exists when they wrap around to the start of the allocation.
+        unsafe {
+            ptr::copy_nonoverlapping(self.buf.add(start), out.as_mut_ptr(), first_len);
+            ptr::copy_nonoverlapping(self.buf, out.as_mut_ptr().add(first_len), count - first_len);
+        }
+
+        count
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        // mask with and is allowed here because size is always a power of two
+        let start = mask_and(self.size, self.readptr);
+        let first_len = self.len().min(self.size - start);
+
+        // SAFETY: the first `len` elements from the readptr are initialized, the second
+        // slice only has elements when they wrap around to the start of the allocation.
+        unsafe {
+            (
+                core::slice::from_raw_parts(self.buf.add(start), first_len),
+                core::slice::from_raw_parts(self.buf, self.len() - first_len),
+            )
+        }
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        // mask with and is allowed here because size is always a power of two
+        let start = mask_and(self.size, self.readptr);
+        let first_len = self.len().min(self.size - start);
+
+        // SAFETY: like in as_slices. The first slice starts at or after the end of the second
+        // one, so they don't overlap.
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(self.buf.add(start), first_len),
+                core::slice::from_raw_parts_mut(self.buf, self.len() - first_len),
+            )
+        }
     }
 
     fn dequeue(&mut self) -> Option<T> {
@@ -277,12 +464,14 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
         get_unchecked_mut,
         readptr,
         writeptr,
+        total_pushed,
         mask_and
     );
 
     #[inline]
     fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
         self.clear();
+        let len = self.len();
 
         self.readptr = 0;
         self.writeptr = self.capacity;
@@ -290,10 +479,114 @@ unsafe impl<T> RingBuffer<T> for AllocRingBuffer<T> {
         for i in 0..self.capacity {
             unsafe { ptr::write(get_unchecked_mut(self, i), f()) };
         }
+        self.total_pushed += self.capacity as u64;
+        self.update_high_water(len);
+    }
+}
+
+impl<T> Default for AllocRingBuffer<T> {
+    /// Creates a buffer with a capacity of
+    /// [`DEFAULT_CAPACITY`](AllocRingBuffer::DEFAULT_CAPACITY).
+    #[inline]
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CAPACITY)
+    }
+}
+
+/// What [`push_with_policy`](AllocRingBuffer::push_with_policy) does when an [`AllocRingBuffer`]
+/// is full, see [`AllocRingBuffer::set_policy`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OverwritePolicy {
+    /// The oldest element is moved out to make room for the new one. This is the default.
+    Overwrite,
+    /// The new element is handed back in `Err`, and the ringbuffer is left unchanged.
+    Reject,
+}
+
+impl Default for OverwritePolicy {
+    fn default() -> Self {
+        Self::Overwrite
+    }
+}
+
+/// Builds an [`AllocRingBuffer`], see [`AllocRingBuffer::builder`].
+#[derive(Debug, Clone, Copy)]
+pub struct AllocRingBufferBuilder<T> {
+    capacity: usize,
+    policy: OverwritePolicy,
+    phantom: PhantomData<T>,
+}
+
+impl<T> AllocRingBufferBuilder<T> {
+    /// Sets the capacity of the ringbuffer. The default is
+    /// [`DEFAULT_CAPACITY`](AllocRingBuffer::DEFAULT_CAPACITY).
+    #[inline]
+    #[must_use]
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+
+    /// Sets what [`push_with_policy`](AllocRingBuffer::push_with_policy) does when the
+    /// ringbuffer is full. The default is [`OverwritePolicy::Overwrite`].
+    #[inline]
+    #[must_use]
+    pub fn policy(mut self, policy: OverwritePolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    /// Creates the ringbuffer.
+    ///
+    /// # Panics
+    /// Panics when the capacity is zero. Use [`try_build`](AllocRingBufferBuilder::try_build)
+    /// to handle that case instead.
+    #[inline]
+    #[must_use]
+    pub fn build(self) -> AllocRingBuffer<T> {
+        let mut rb = AllocRingBuffer::new(self.capacity);
+        rb.policy = self.policy;
+        rb
+    }
+
+    /// Creates the ringbuffer, like [`build`](AllocRingBufferBuilder::build).
+    ///
+    /// # Errors
+    /// Returns [`RingBufferError::InvalidCapacity`] when the capacity is zero.
+    #[inline]
+    pub fn try_build(self) -> Result<AllocRingBuffer<T>, RingBufferError> {
+        let mut rb = AllocRingBuffer::try_new(self.capacity)?;
+        rb.policy = self.policy;
+        Ok(rb)
     }
 }
 
 impl<T> AllocRingBuffer<T> {
+    /// The capacity of ringbuffers created by [`Default::default`] and
+    /// [`builder`](AllocRingBuffer::builder), unless another one is set.
+    pub const DEFAULT_CAPACITY: usize = 1024;
+
+    /// Returns a builder to configure and create an `AllocRingBuffer`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::<u8>::builder().capacity(16).build();
+    /// assert_eq!(rb.capacity(), 16);
+    ///
+    /// let rb = AllocRingBuffer::<u8>::builder().build();
+    /// assert_eq!(rb.capacity(), AllocRingBuffer::<u8>::DEFAULT_CAPACITY);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn builder() -> AllocRingBufferBuilder<T> {
+        AllocRingBufferBuilder {
+            capacity: Self::DEFAULT_CAPACITY,
+            policy: OverwritePolicy::Overwrite,
+            phantom: PhantomData,
+        }
+    }
+
     /// Creates a `AllocRingBuffer` with a certain capacity. The actual capacity is the input to the
     /// function raised to the power of two (effectively the input is the log2 of the actual capacity)
     #[inline]
@@ -303,7 +596,8 @@ impl<T> AllocRingBuffer<T> {
     }
 
     #[inline]
-    /// Alias of [`with_capacity`](AllocRingBuffer::new).
+    /// Alias of [`new`](AllocRingBuffer::new). The capacity is exactly `cap`, only the
+    /// allocation is rounded up to a power of two.
     #[must_use]
     #[deprecated = "alias of new"]
     pub fn with_capacity(cap: usize) -> Self {
@@ -312,109 +606,1092 @@ impl<T> AllocRingBuffer<T> {
 
     /// Creates a `AllocRingBuffer` with a certain capacity. The capacity must not be zero.
     ///
+    /// The ringbuffer holds exactly `capacity` elements, which is what
+    /// [`capacity`](RingBuffer::capacity) returns. The allocation backing it is rounded up to the
+    /// next power of two though, so that indices can be wrapped with a bitmask. Its size in
+    /// elements is returned by [`buffer_size`](RingBuffer::buffer_size). Pass a power of two to
+    /// make both equal.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::<u8>::new(100);
+    /// assert_eq!(rb.capacity(), 100);
+    /// assert_eq!(rb.buffer_size(), 128);
+    ///
+    /// let rb = AllocRingBuffer::<u8>::new(128);
+    /// assert_eq!(rb.capacity(), rb.buffer_size());
+    /// ```
+    ///
     /// # Panics
-    /// Panics when capacity is zero
+    /// Panics when capacity is zero. Use [`try_new`](AllocRingBuffer::try_new) to handle that
+    /// case instead.
     #[inline]
     #[must_use]
     pub fn new(capacity: usize) -> Self {
-        assert_ne!(capacity, 0, "Capacity must be greater than 0");
-        let size = capacity.next_power_of_two();
-        let layout = alloc::alloc::Layout::array::<T>(size).unwrap();
-        let buf = unsafe { alloc::alloc::alloc(layout) as *mut T };
-        Self {
+        match Self::try_new(capacity) {
+            Ok(rb) => rb,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Creates a `AllocRingBuffer` with a certain capacity, like [`new`](AllocRingBuffer::new).
+    ///
+    /// # Errors
+    /// Returns [`RingBufferError::InvalidCapacity`] when capacity is zero,
+    /// [`RingBufferError::CapacityOverflow`] when the allocation would be too large, and
+    /// [`RingBufferError::AllocationFailed`] when the allocator fails.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer, RingBufferError};
+    ///
+    /// assert_eq!(AllocRingBuffer::<u8>::try_new(4).unwrap().capacity(), 4);
+    /// assert_eq!(AllocRingBuffer::<u8>::try_new(0), Err(RingBufferError::InvalidCapacity));
+    /// ```
+    pub fn try_new(capacity: usize) -> Result<Self, RingBufferError> {
+        if capacity == 0 {
+            return Err(RingBufferError::InvalidCapacity);
+        }
+
+        let size = capacity
+            .checked_next_power_of_two()
+            .ok_or(RingBufferError::CapacityOverflow)?;
+        let layout = alloc::alloc::Layout::array::<T>(size)
+            .map_err(|_| RingBufferError::CapacityOverflow)?;
+        let buf = if layout.size() == 0 {
+            // allocating zero bytes is undefined behaviour, and zero sized types don't need memory
+            ptr::NonNull::dangling().as_ptr()
+        } else {
+            let buf = unsafe { alloc::alloc::alloc(layout) as *mut T };
+            if buf.is_null() {
+                return Err(RingBufferError::AllocationFailed);
+            }
+            buf
+        };
+
+        Ok(Self {
             buf,
             size,
             capacity,
             readptr: 0,
             writeptr: 0,
+            overwrite_count: 0,
+            total_pushed: 0,
+            policy: OverwritePolicy::Overwrite,
+            high_water: None,
+            high_water_reached: false,
+        })
+    }
+
+    /// Creates a full `AllocRingBuffer` with a capacity of `cap` from the items of `iter`.
+    /// If `iter` doesn't yield exactly `cap` items, returns the number of items it yielded
+    /// instead. To count them, `iter` is consumed completely even when it yields too many.
+    ///
+    /// # Panics
+    /// Panics when `cap` is zero
+    ///
+    /// # Errors
+    /// Returns `Err` with the number of items `iter` yielded, if that is not `cap`.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from_exact_iter(3, 1..=3).unwrap();
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+    /// assert!(rb.is_full());
+    ///
+    /// assert_eq!(AllocRingBuffer::from_exact_iter(3, 1..=2), Err(2));
+    /// assert_eq!(AllocRingBuffer::from_exact_iter(3, 1..=5), Err(5));
+    /// ```
+    pub fn from_exact_iter<I: IntoIterator<Item = T>>(cap: usize, iter: I) -> Result<Self, usize> {
+        let mut res = Self::new(cap);
+        let mut iter = iter.into_iter();
+
+        res.extend(iter.by_ref().take(cap));
+        if res.len() < cap {
+            return Err(res.len());
+        }
+
+        match iter.count() {
+            0 => Ok(res),
+            remaining => Err(cap + remaining),
         }
     }
-}
 
-/// Get a reference from the buffer without checking it is initialized.
-///
-/// Caller must be sure the index is in bounds, or this will panic.
-#[inline]
-unsafe fn get_unchecked<'a, T>(rb: *const AllocRingBuffer<T>, index: usize) -> &'a T {
-    let p = (*rb).buf.add(index);
-    // Safety: caller makes sure the index is in bounds for the ringbuffer.
-    // All in bounds values in the ringbuffer are initialized
-    &*p
-}
+    /// Creates an `AllocRingBuffer` with a capacity of `cap` and pushes all items of `iter`
+    /// into it. Whenever a push overwrites the oldest element, that element is passed to
+    /// `on_drop` instead of being dropped silently.
+    ///
+    /// # Panics
+    /// Panics when `cap` is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut dropped = Vec::new();
+    /// let rb = AllocRingBuffer::from_iter_lossy(2, 1..=4, |i| dropped.push(i));
+    /// assert_eq!(rb.to_vec(), vec![3, 4]);
+    /// assert_eq!(dropped, vec![1, 2]);
+    /// ```
+    pub fn from_iter_lossy<I: IntoIterator<Item = T>, F: FnMut(T)>(
+        cap: usize,
+        iter: I,
+        mut on_drop: F,
+    ) -> Self {
+        let mut res = Self::new(cap);
+        for i in iter {
+            if let Some(evicted) = res.push_force_pop(i) {
+                on_drop(evicted);
+            }
+        }
+        res
+    }
 
-/// Get a mut reference from the buffer without checking it is initialized.
-///
-/// Caller must be sure the index is in bounds, or this will panic.
-#[inline]
-unsafe fn get_unchecked_mut<T>(rb: *mut AllocRingBuffer<T>, index: usize) -> *mut T {
-    let p = (*rb).buf.add(index);
+    /// Creates a *full* `AllocRingBuffer` with a certain capacity, where every slot holds a
+    /// clone of `value`. Unlike [`new`](AllocRingBuffer::new), which starts out empty,
+    /// [`is_full`](RingBuffer::is_full) is true right away and `len() == capacity`.
+    ///
+    /// The buffer is in the same state as after pushing `value` `capacity` times into an empty
+    /// one: the read pointer is at index 0, the write pointer at `capacity`, and the `capacity`
+    /// clones are dequeued before anything pushed later. Every further push overwrites one of
+    /// them, which makes this useful for a delay line of a fixed length.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::filled(3, 0);
+    /// assert!(rb.is_full());
+    /// assert_eq!(rb.to_vec(), vec![0, 0, 0]);
+    ///
+    /// rb.push(1);
+    /// assert_eq!(rb.dequeue(), Some(0));
+    /// assert_eq!(rb.to_vec(), vec![0, 1]);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics when capacity is zero.
+    #[must_use]
+    pub fn filled(capacity: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut res = Self::new(capacity);
+        res.extend(core::iter::repeat(value).take(capacity));
+        res
+    }
 
-    // Safety: caller makes sure the index is in bounds for the ringbuffer.
-    // All in bounds values in the ringbuffer are initialized
-    p.cast()
-}
+    /// Concatenates the elements of `buffers` into a new `AllocRingBuffer`, moving them. The
+    /// elements keep their order: those of earlier buffers come before those of later ones, so
+    /// the oldest element of the first buffer ends up being the oldest overall.
+    ///
+    /// The new ringbuffer has a capacity of the total number of elements, so it is full, and
+    /// its allocation is rounded up to the next power of two like with
+    /// [`new`](AllocRingBuffer::new). When all buffers are empty, the capacity is 1.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let a = AllocRingBuffer::from([1, 2]);
+    /// let b = AllocRingBuffer::from([3, 4, 5]);
+    ///
+    /// let rb = AllocRingBuffer::concat([a, b]);
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3, 4, 5]);
+    /// assert_eq!(rb.capacity(), 5);
+    /// assert_eq!(rb.buffer_size(), 8);
+    /// ```
+    #[must_use]
+    pub fn concat<I: IntoIterator<Item = Self>>(buffers: I) -> Self {
+        let buffers: alloc::vec::Vec<Self> = buffers.into_iter().collect();
+        let total = buffers.iter().map(RingBuffer::len).sum::<usize>();
 
-impl<T> Index<usize> for AllocRingBuffer<T> {
-    type Output = T;
+        let mut res = Self::new(total.max(1));
+        for buffer in buffers {
+            res.extend(buffer);
+        }
+        res
+    }
 
-    fn index(&self, index: usize) -> &Self::Output {
-        self.get(index).expect("index out of bounds")
+    /// Returns the number of elements which were overwritten by [`push`](RingBuffer::push)
+    /// because the buffer was full, since the buffer was created or
+    /// [`reset_overwrite_count`](AllocRingBuffer::reset_overwrite_count) was last called.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// assert_eq!(rb.overwrite_count(), 3);
+    ///
+    /// rb.reset_overwrite_count();
+    /// assert_eq!(rb.overwrite_count(), 0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn overwrite_count(&self) -> u64 {
+        self.overwrite_count
     }
-}
 
-impl<T> IndexMut<usize> for AllocRingBuffer<T> {
-    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
-        self.get_mut(index).expect("index out of bounds")
+    /// Resets the number returned by [`overwrite_count`](AllocRingBuffer::overwrite_count)
+    /// to zero.
+    #[inline]
+    pub fn reset_overwrite_count(&mut self) {
+        self.overwrite_count = 0;
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use crate::{AllocRingBuffer, RingBuffer};
+    /// Returns what [`push_with_policy`](AllocRingBuffer::push_with_policy) does when the
+    /// ringbuffer is full.
+    #[inline]
+    #[must_use]
+    pub fn policy(&self) -> OverwritePolicy {
+        self.policy
+    }
 
-    // just test that this compiles
-    #[test]
-    fn test_generic_clone() {
-        fn helper(a: &AllocRingBuffer<i32>) -> AllocRingBuffer<i32> {
-            a.clone()
+    /// Sets what [`push_with_policy`](AllocRingBuffer::push_with_policy) does when the
+    /// ringbuffer is full. The policy lets the code that creates a ringbuffer decide whether
+    /// the code filling it may overwrite elements.
+    ///
+    /// Only `push_with_policy` follows the policy. [`push`](RingBuffer::push), and everything
+    /// built on it like [`extend`](Extend::extend), always overwrites the oldest element, and
+    /// [`try_push`](RingBuffer::try_push) always rejects the new one.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, OverwritePolicy, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.set_policy(OverwritePolicy::Reject);
+    /// rb.extend([1, 2]);
+    /// assert_eq!(rb.push_with_policy(3), Err(3));
+    ///
+    /// rb.push(3);
+    /// assert_eq!(rb.to_vec(), vec![2, 3]);
+    /// ```
+    #[inline]
+    pub fn set_policy(&mut self, policy: OverwritePolicy) {
+        self.policy = policy;
+    }
+
+    /// Pushes a value onto the buffer, and follows the [`policy`](AllocRingBuffer::policy) when
+    /// the buffer is full: with [`OverwritePolicy::Overwrite`], the oldest element is moved out
+    /// and returned in [`PushResult::Overwrote`], like
+    /// [`push_overwriting`](RingBuffer::push_overwriting) does. With
+    /// [`OverwritePolicy::Reject`], the buffer is left unchanged and `value` is handed back in
+    /// `Err`, like [`try_push`](RingBuffer::try_push) does.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, OverwritePolicy, PushResult};
+    ///
+    /// let mut rb = AllocRingBuffer::new(1);
+    /// assert_eq!(rb.push_with_policy(1), Ok(PushResult::Inserted));
+    /// assert_eq!(rb.push_with_policy(2), Ok(PushResult::Overwrote(1)));
+    ///
+    /// rb.set_policy(OverwritePolicy::Reject);
+    /// assert_eq!(rb.push_with_policy(3), Err(3));
+    /// ```
+    pub fn push_with_policy(&mut self, value: T) -> Result<PushResult<T>, T> {
+        match self.policy {
+            OverwritePolicy::Overwrite => Ok(self.push_overwriting(value)),
+            OverwritePolicy::Reject => self.try_push(value).map(|()| PushResult::Inserted),
         }
+    }
 
-        _ = helper(&AllocRingBuffer::new(2));
-        _ = helper(&AllocRingBuffer::new(5));
+    /// Sets the fill level at which [`high_water_reached`](AllocRingBuffer::high_water_reached)
+    /// becomes true. The flag is set by a push which makes [`len`](RingBuffer::len) cross the
+    /// threshold, that is when the length was below `threshold` before and is at least
+    /// `threshold` after. This can be used to flush the ringbuffer before elements start being
+    /// overwritten. The flag is cleared, so a buffer which is already filled past `threshold`
+    /// has to drop below it first.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.set_high_water(3);
+    /// rb.extend([1, 2]);
+    /// assert!(!rb.high_water_reached());
+    ///
+    /// rb.push(3);
+    /// assert!(rb.high_water_reached());
+    ///
+    /// let _ = rb.dequeue();
+    /// assert!(!rb.high_water_reached());
+    /// ```
+    ///
+    /// # Panics
+    /// Panics when `threshold` is zero, since the length can never cross it.
+    #[inline]
+    pub fn set_high_water(&mut self, threshold: usize) {
+        assert_ne!(threshold, 0, "high water threshold must be greater than 0");
+        self.high_water = Some(threshold);
+        self.high_water_reached = false;
     }
 
-    #[test]
-    fn test_not_power_of_two() {
-        let mut rb = AllocRingBuffer::new(10);
-        const NUM_VALS: usize = 1000;
+    /// Returns whether a push made the length of the ringbuffer cross the threshold set with
+    /// [`set_high_water`](AllocRingBuffer::set_high_water). Becomes false again when the
+    /// length drops below the threshold, or when
+    /// [`reset_high_water`](AllocRingBuffer::reset_high_water) is called. Always false when no
+    /// threshold is set.
+    #[inline]
+    #[must_use]
+    pub fn high_water_reached(&self) -> bool {
+        match self.high_water {
+            Some(threshold) => self.high_water_reached && self.len() >= threshold,
+            None => false,
+        }
+    }
 
-        // recycle the ringbuffer a bunch of time to see if noneof the logic
-        // messes up
-        for _ in 0..100 {
-            for i in 0..NUM_VALS {
-                rb.enqueue(i);
-            }
-            assert!(rb.is_full());
+    /// Clears the flag returned by [`high_water_reached`](AllocRingBuffer::high_water_reached),
+    /// until a push makes the length cross the threshold again.
+    #[inline]
+    pub fn reset_high_water(&mut self) {
+        self.high_water_reached = false;
+    }
 
-            for i in 0..10 {
-                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue())
+    // called by every method which pushes, with the length from before the push
+    #[inline]
+    fn update_high_water(&mut self, len: usize) {
+        if let Some(threshold) = self.high_water {
+            if len < threshold {
+                self.high_water_reached = self.len() >= threshold;
             }
-
-            assert!(rb.is_empty())
         }
     }
 
-    #[test]
-    fn test_with_capacity_power_of_two() {
-        let b = AllocRingBuffer::<i32>::with_capacity_power_of_2(2);
-        assert_eq!(b.capacity, 4);
+    /// Returns the total number of elements pushed into the ringbuffer since it was created,
+    /// including the ones which were overwritten or dequeued since. Comparing it to an earlier
+    /// value tells how many elements were pushed in between. [`fill`](RingBuffer::fill) counts
+    /// as pushing every slot, while methods which only reorder or remove elements, like
+    /// [`rotate_left`](RingBuffer::rotate_left) or [`dedup`](RingBuffer::dedup), don't count.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// let baseline = rb.total_pushed();
+    /// assert_eq!(baseline, 5);
+    ///
+    /// rb.push(6);
+    /// let _ = rb.dequeue();
+    /// assert_eq!(rb.total_pushed() - baseline, 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn total_pushed(&self) -> u64 {
+        self.total_pushed
     }
 
-    #[test]
-    #[should_panic]
-    fn test_index_zero_length() {
-        let b = AllocRingBuffer::<i32>::new(2);
-        let _ = b[2];
+    /// Returns a pointer to the start of the backing allocation. It holds
+    /// [`buffer_size`](RingBuffer::buffer_size) contiguous slots of `T`, of which at most
+    /// [`capacity`](RingBuffer::capacity) are used. Not every slot is initialized: the elements
+    /// start at [`read_index`](AllocRingBuffer::read_index) and wrap around the end of the
+    /// allocation.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        self.buf
+    }
+
+    /// Returns a mutable pointer to the start of the backing allocation, see
+    /// [`as_ptr`](AllocRingBuffer::as_ptr). This makes it possible to write into the free
+    /// slots, starting at [`write_index`](AllocRingBuffer::write_index), and then commit
+    /// those writes with [`advance_write`](AllocRingBuffer::advance_write).
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf
+    }
+
+    /// Returns the index in the backing allocation of the oldest element, which is dequeued next.
+    #[inline]
+    #[must_use]
+    pub fn read_index(&self) -> usize {
+        mask_and(self.size, self.readptr)
+    }
+
+    /// Returns the index in the backing allocation of the slot the next element is pushed into.
+    #[inline]
+    #[must_use]
+    pub fn write_index(&self) -> usize {
+        mask_and(self.size, self.writeptr)
+    }
+
+    /// Adds the `n` slots starting at [`write_index`](AllocRingBuffer::write_index), wrapping
+    /// around the end of the allocation of [`buffer_size`](RingBuffer::buffer_size) slots, to the
+    /// elements of the ringbuffer, as if they had been pushed.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than the number of free slots, `capacity - len`
+    ///
+    /// # Safety
+    /// The caller must have initialized those `n` slots with valid values of `T`, for example
+    /// by writing through [`as_mut_ptr`](AllocRingBuffer::as_mut_ptr). The ringbuffer takes
+    /// ownership of them, and drops them like any other element.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::<u8>::new(4);
+    /// rb.extend([1, 2, 3]);
+    /// let _ = rb.dequeue();
+    ///
+    /// // write two bytes behind the newest element, wrapping around the end of the allocation
+    /// let (start, size) = (rb.write_index(), rb.buffer_size());
+    /// let ptr = rb.as_mut_ptr();
+    /// for (offset, byte) in [4, 5].into_iter().enumerate() {
+    ///     unsafe { ptr.add((start + offset) % size).write(byte) };
+    /// }
+    /// unsafe { rb.advance_write(2) };
+    ///
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4, 5]);
+    /// ```
+    pub unsafe fn advance_write(&mut self, n: usize) {
+        assert!(
+            n <= self.capacity - self.len(),
+            "not enough free slots to advance over"
+        );
+
+        let len = self.len();
+        self.writeptr += n;
+        self.total_pushed += n as u64;
+        self.update_high_water(len);
+    }
+
+    /// Removes the `n` oldest elements from the ringbuffer without dropping them. This is the
+    /// counterpart of [`advance_write`](AllocRingBuffer::advance_write) for reading the elements
+    /// through [`as_ptr`](AllocRingBuffer::as_ptr): the caller takes ownership of them, for
+    /// example by moving them out with [`ptr::read`](core::ptr::read). Elements which aren't
+    /// read are leaked. Use [`skip_n`](RingBuffer::skip_n) to drop them instead.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than `len`
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::<u8>::from([1, 2, 3, 4]);
+    /// let first = unsafe { rb.as_ptr().add(rb.read_index()).read() };
+    /// rb.advance_read(1);
+    /// assert_eq!(first, 1);
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    /// ```
+    pub fn advance_read(&mut self, n: usize) {
+        assert!(n <= self.len(), "not enough elements to advance over");
+
+        self.readptr += n;
+    }
+
+    /// Returns an iterator over the elements, oldest first, paired with their sequence number:
+    /// the number of elements pushed before them since the ringbuffer was created. The newest
+    /// element has number [`total_pushed`](AllocRingBuffer::total_pushed) - 1.
+    ///
+    /// The number of the element at index `i` is `total_pushed - len + i`, so the numbers stay
+    /// accurate as long as elements are only pushed to the back and removed from the front.
+    /// Methods which remove elements from the middle, like [`remove`](RingBuffer::remove),
+    /// [`retain`](RingBuffer::retain), [`swap_remove_back`](RingBuffer::swap_remove_back),
+    /// [`swap_remove_front`](RingBuffer::swap_remove_front) and [`dedup`](RingBuffer::dedup),
+    /// renumber the survivors that are older than the removed elements.
+    /// [`clear`](RingBuffer::clear) doesn't reset them.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.extend(['a', 'b', 'c']);
+    /// assert_eq!(rb.iter_enumerated().collect::<Vec<_>>(), [(1, &'b'), (2, &'c')]);
+    ///
+    /// rb.clear();
+    /// rb.push('d');
+    /// assert_eq!(rb.iter_enumerated().collect::<Vec<_>>(), [(3, &'d')]);
+    /// ```
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (u64, &T)> {
+        (self.total_pushed.saturating_sub(self.len() as u64)..).zip(self.iter())
+    }
+
+    /// Moves the elements out of the ringbuffer into two boxed slices, consuming the buffer.
+    ///
+    /// The split matches the layout of the elements in memory: the first slice holds the
+    /// elements from the read position up to the end of the allocation, the second slice
+    /// holds the elements that wrapped around to the start. When the contents do not wrap,
+    /// the second slice is empty. Concatenating the two slices yields the elements in dequeue order.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(4);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    ///
+    /// let (a, b) = rb.into_slices();
+    /// assert_eq!(&*a, &[2, 3, 4]);
+    /// assert_eq!(&*b, &[5]);
+    /// ```
+    #[must_use]
+    pub fn into_slices(mut self) -> (alloc::boxed::Box<[T]>, alloc::boxed::Box<[T]>) {
+        let len = self.len();
+        let first_len = len.min(self.size - mask_and(self.size, self.readptr));
+
+        let first: alloc::vec::Vec<T> = (0..first_len).filter_map(|_| self.dequeue()).collect();
+        let second: alloc::vec::Vec<T> = self.drain().collect();
+
+        (first.into_boxed_slice(), second.into_boxed_slice())
+    }
+
+    /// Moves the elements out of the ringbuffer into a vector, in dequeue order.
+    /// Unlike [`to_vec`](RingBuffer::to_vec), this doesn't clone the elements.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(2);
+    /// rb.extend([1, 2, 3]);
+    /// assert_eq!(rb.into_vec(), vec![2, 3]);
+    /// ```
+    #[must_use]
+    pub fn into_vec(self) -> alloc::vec::Vec<T> {
+        self.into_iter().collect()
+    }
+
+    /// Consumes the ringbuffer and applies `f` to every element in dequeue order,
+    /// returning a new ringbuffer with the same capacity holding the results.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(3);
+    /// rb.extend([1, 2, 3, 4]);
+    ///
+    /// let mapped = rb.map(|i| i as f32 / 2.0);
+    /// assert_eq!(mapped.capacity(), 3);
+    /// assert_eq!(mapped.to_vec(), vec![1.0, 1.5, 2.0]);
+    /// ```
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> AllocRingBuffer<U> {
+        let mut res = AllocRingBuffer::new(self.capacity);
+        res.policy = self.policy;
+        res.extend(self.into_iter().map(f));
+        res
+    }
+
+    /// Changes the capacity of the ringbuffer to `capacity`, keeping the newest
+    /// `min(len, capacity)` elements in order. Older elements which don't fit anymore are
+    /// dropped. Like with [`new`](AllocRingBuffer::new), the allocation is rounded up to the
+    /// next power of two, and it is only replaced when that size changes.
+    ///
+    /// # Panics
+    /// Panics when capacity is zero
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3, 4, 5]);
+    /// rb.set_capacity(3);
+    /// assert_eq!(rb.to_vec(), vec![3, 4, 5]);
+    ///
+    /// rb.set_capacity(6);
+    /// rb.extend([6, 7, 8]);
+    /// assert_eq!(rb.to_vec(), vec![3, 4, 5, 6, 7, 8]);
+    /// ```
+    pub fn set_capacity(&mut self, capacity: usize) {
+        assert_ne!(capacity, 0, "Capacity must be greater than 0");
+
+        while self.len() > capacity {
+            let _ = self.dequeue();
+        }
+
+        if capacity.next_power_of_two() == self.size {
+            self.capacity = capacity;
+        } else {
+            self.move_into(Self::new(capacity));
+        }
+    }
+
+    /// Makes sure that at least `additional` more elements can be pushed without overwriting
+    /// any, like [`Vec::try_reserve`](alloc::vec::Vec::try_reserve). If there are fewer free
+    /// slots, the capacity grows to `len + additional`. The allocation is only replaced when it
+    /// is too small for that, and like with [`new`](AllocRingBuffer::new) the new one is rounded
+    /// up to the next power of two.
+    ///
+    /// # Errors
+    /// Returns [`RingBufferError::CapacityOverflow`] when the new capacity would be too large,
+    /// and [`RingBufferError::AllocationFailed`] when the allocator fails. The ringbuffer is
+    /// left unchanged then.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer, RingBufferError};
+    ///
+    /// let mut rb = AllocRingBuffer::from([1, 2, 3]);
+    /// rb.try_reserve(2).unwrap();
+    /// assert_eq!(rb.capacity(), 5);
+    ///
+    /// rb.extend([4, 5]);
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3, 4, 5]);
+    ///
+    /// assert_eq!(rb.try_reserve(usize::MAX), Err(RingBufferError::CapacityOverflow));
+    /// assert_eq!(rb.capacity(), 5);
+    /// ```
+    pub fn try_reserve(&mut self, additional: usize) -> Result<(), RingBufferError> {
+        let capacity = self
+            .len()
+            .checked_add(additional)
+            .ok_or(RingBufferError::CapacityOverflow)?;
+
+        if capacity <= self.capacity {
+            return Ok(());
+        }
+        if capacity <= self.size {
+            self.capacity = capacity;
+            return Ok(());
+        }
+
+        self.move_into(Self::try_new(capacity)?);
+        Ok(())
+    }
+
+    // moves the elements and settings of self into the empty ringbuffer `res`, and replaces
+    // self with it. `res` must be able to hold `len` elements.
+    fn move_into(&mut self, mut res: Self) {
+        debug_assert!(res.is_empty() && res.capacity >= self.len());
+
+        res.extend(self.drain());
+        res.overwrite_count = self.overwrite_count;
+        res.total_pushed = self.total_pushed;
+        res.policy = self.policy;
+        res.high_water = self.high_water;
+        res.high_water_reached = self.high_water_reached;
+        *self = res;
+    }
+
+    /// Splits the ringbuffer into two at the given index. Afterwards `self` contains the elements
+    /// `0..at`, and the returned ringbuffer contains the elements `at..len`, both in dequeue
+    /// order. The returned ringbuffer has the same capacity as `self`.
+    ///
+    /// # Panics
+    /// Panics if `at > len`
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(5);
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// let tail = rb.split_off(2);
+    /// assert_eq!(rb.to_vec(), vec![1, 2]);
+    /// assert_eq!(tail.to_vec(), vec![3, 4, 5]);
+    /// ```
+    #[must_use = "use `truncate_front` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "`at` out of bounds");
+
+        // move the elements `at..len` to the front, so they can be dequeued in order
+        self.rotate_left(at);
+        let mut res = Self::new(self.capacity);
+        res.policy = self.policy;
+        res.extend((at..len).filter_map(|_| self.dequeue()));
+        res
+    }
+
+    /// Splits the ringbuffer into two, consuming it. The oldest `len / 2` elements are moved
+    /// into the first ringbuffer and the rest into the second, both in dequeue order.
+    /// Both ringbuffers have the same capacity as the original.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5]);
+    /// let (a, b) = rb.split_half();
+    /// assert_eq!(a.to_vec(), vec![1, 2]);
+    /// assert_eq!(b.to_vec(), vec![3, 4, 5]);
+    /// ```
+    #[must_use]
+    pub fn split_half(self) -> (Self, Self) {
+        let capacity = self.capacity;
+        let policy = self.policy;
+        let half = self.len() / 2;
+
+        let mut iter = self.into_iter();
+        let mut first = Self::new(capacity);
+        first.policy = policy;
+        first.extend(iter.by_ref().take(half));
+        let mut second = Self::new(capacity);
+        second.policy = policy;
+        second.extend(iter);
+
+        (first, second)
+    }
+
+    /// Moves the elements out of the ringbuffer in dequeue order, grouped into vectors
+    /// of `size` elements. The last chunk may be shorter.
+    ///
+    /// # Panics
+    /// Panics when `size` is zero
+    ///
+    /// ```
+    /// use ringbuffer::AllocRingBuffer;
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2, 3, 4, 5]);
+    /// let chunks: Vec<_> = rb.into_chunks(2).collect();
+    /// assert_eq!(chunks, vec![vec![1, 2], vec![3, 4], vec![5]]);
+    /// ```
+    pub fn into_chunks(self, size: usize) -> impl Iterator<Item = alloc::vec::Vec<T>> {
+        assert_ne!(size, 0, "Chunk size must be greater than 0");
+
+        let mut iter = self.into_iter();
+        core::iter::from_fn(move || {
+            let chunk: alloc::vec::Vec<T> = iter.by_ref().take(size).collect();
+            if chunk.is_empty() {
+                None
+            } else {
+                Some(chunk)
+            }
+        })
+    }
+
+    /// Moves the elements into a [`SpscRingBuffer`](crate::SpscRingBuffer) with the same capacity,
+    /// and splits that into a [`Producer`] and a [`Consumer`] which can be used from two different
+    /// threads without locking. See [`SpscRingBuffer`](crate::SpscRingBuffer) for details.
+    ///
+    /// ```
+    /// use ringbuffer::AllocRingBuffer;
+    ///
+    /// let rb = AllocRingBuffer::from([1, 2]);
+    /// let (mut producer, mut consumer) = rb.split();
+    ///
+    /// // the buffer is still full
+    /// assert_eq!(producer.push(3), Err(3));
+    ///
+    /// assert_eq!(consumer.pop(), Some(1));
+    /// assert_eq!(producer.push(3), Ok(()));
+    /// ```
+    #[cfg(feature = "sync")]
+    #[must_use]
+    pub fn split(self) -> (Producer<T>, Consumer<T>) {
+        SpscRingBuffer::from(self).split()
+    }
+}
+
+/// The modulus of the Adler-32 checksum, the largest prime smaller than 2^16.
+const ADLER_MOD: u64 = 65521;
+
+impl AllocRingBuffer<u8> {
+    /// Computes an Adler-32 checksum over the bytes in the ringbuffer, in dequeue order.
+    ///
+    /// Combined with [`rolling_hasher`](AllocRingBuffer::rolling_hasher), this can be used as a
+    /// rolling hash over a window of bytes, for example for content defined chunking.
+    #[must_use]
+    pub fn rolling_hash(&self) -> u32 {
+        let (a, b) = self.iter().fold((1, 0), |(a, b), &i| {
+            let a = (a + u64::from(i)) % ADLER_MOD;
+            (a, (b + a) % ADLER_MOD)
+        });
+
+        ((b << 16) | a) as u32
+    }
+
+    /// Returns a [`RollingHasher`] which pushes bytes onto this ringbuffer and keeps its
+    /// [`rolling_hash`](AllocRingBuffer::rolling_hash) up to date in constant time per byte.
+    /// Creating it computes the hash once, in O(len) time.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::from(*b"abcd");
+    /// let mut hasher = rb.rolling_hasher();
+    ///
+    /// let (evicted, hash) = hasher.roll(b'e');
+    /// assert_eq!(evicted, b'a');
+    /// assert_eq!(hash, AllocRingBuffer::from(*b"bcde").rolling_hash());
+    /// ```
+    pub fn rolling_hasher(&mut self) -> RollingHasher<'_> {
+        let hash = self.rolling_hash();
+        RollingHasher { rb: self, hash }
+    }
+}
+
+/// Pushes bytes onto an [`AllocRingBuffer<u8>`] while keeping the Adler-32 checksum of its
+/// contents, see [`rolling_hash`](AllocRingBuffer::rolling_hash). Created by
+/// [`rolling_hasher`](AllocRingBuffer::rolling_hasher).
+///
+/// The hasher borrows the ringbuffer mutably, so the bytes can't change behind its back. To
+/// modify them in another way, drop the hasher and create a new one afterwards.
+#[derive(Debug)]
+pub struct RollingHasher<'a> {
+    rb: &'a mut AllocRingBuffer<u8>,
+    hash: u32,
+}
+
+impl RollingHasher<'_> {
+    /// Pushes `incoming` onto the ringbuffer, evicting the oldest byte if it is full, and
+    /// returns the evicted byte together with the hash of the ringbuffer after the push, in
+    /// constant time. If the buffer wasn't full, nothing is evicted and the returned byte is 0.
+    pub fn roll(&mut self, incoming: u8) -> (u8, u32) {
+        let a = u64::from(self.hash & 0xffff);
+        let b = u64::from(self.hash >> 16);
+
+        let evicted = if self.rb.is_full() {
+            self.rb.dequeue()
+        } else {
+            None
+        };
+        let outgoing = evicted.map_or(0, u64::from);
+        let removed_count = u64::from(evicted.is_some());
+        // the number of bytes the outgoing byte was counted for in b
+        let window = (self.rb.len() as u64 + removed_count) % ADLER_MOD;
+
+        self.rb.push(incoming);
+
+        let a = (a + ADLER_MOD - outgoing + u64::from(incoming)) % ADLER_MOD;
+        let b =
+            (b + 2 * ADLER_MOD - (window * outgoing) % ADLER_MOD + a - removed_count) % ADLER_MOD;
+        self.hash = ((b << 16) | a) as u32;
+
+        (evicted.unwrap_or(0), self.hash)
+    }
+
+    /// Returns the hash of the bytes currently in the ringbuffer.
+    #[must_use]
+    pub fn hash(&self) -> u32 {
+        self.hash
+    }
+
+    /// Returns the ringbuffer the bytes are pushed onto.
+    #[must_use]
+    pub fn buffer(&self) -> &AllocRingBuffer<u8> {
+        self.rb
+    }
+}
+
+/// Get a reference from the buffer without checking it is initialized.
+///
+/// Caller must be sure the index is in bounds, or this will panic.
+#[inline]
+unsafe fn get_unchecked<'a, T>(rb: *const AllocRingBuffer<T>, index: usize) -> &'a T {
+    let p = (*rb).buf.add(index);
+    // Safety: caller makes sure the index is in bounds for the ringbuffer.
+    // All in bounds values in the ringbuffer are initialized
+    &*p
+}
+
+/// Get a mut reference from the buffer without checking it is initialized.
+///
+/// Caller must be sure the index is in bounds, or this will panic.
+#[inline]
+unsafe fn get_unchecked_mut<T>(rb: *mut AllocRingBuffer<T>, index: usize) -> *mut T {
+    let p = (*rb).buf.add(index);
+
+    // Safety: caller makes sure the index is in bounds for the ringbuffer.
+    // All in bounds values in the ringbuffer are initialized
+    p.cast()
+}
+
+impl<T> Index<usize> for AllocRingBuffer<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        self.get(index).expect("index out of bounds")
+    }
+}
+
+impl<T> IndexMut<usize> for AllocRingBuffer<T> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        self.get_mut(index).expect("index out of bounds")
+    }
+}
+
+/// Reads the bytes in dequeue order. [`chunk`](bytes::Buf::chunk) returns the bytes up to the end
+/// of the allocation, the rest follows after advancing past them.
+///
+/// Note that with both traits in scope, `rb.copy_to_slice(..)` calls
+/// [`RingBuffer::copy_to_slice`], which doesn't consume the bytes. Call
+/// `Buf::copy_to_slice(&mut rb, ..)` to read them.
+#[cfg(feature = "bytes")]
+impl bytes::Buf for AllocRingBuffer<u8> {
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len()
+    }
+
+    fn chunk(&self) -> &[u8] {
+        // mask with and is allowed here because size is always a power of two
+        let start = mask_and(self.size, self.readptr);
+        let len = self.len().min(self.size - start);
+
+        // SAFETY: the `len` bytes from the readptr are initialized and don't wrap around
+        unsafe { core::slice::from_raw_parts(self.buf.add(start), len) }
+    }
+
+    fn advance(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.len(),
+            "cannot advance past the end of the ringbuffer"
+        );
+        self.readptr += cnt;
+    }
+}
+
+/// Writes bytes into the free slots of the ringbuffer. Unlike [`push`](RingBuffer::push),
+/// writing through `BufMut` never overwrites bytes which haven't been read yet:
+/// [`remaining_mut`](bytes::BufMut::remaining_mut) is the number of free slots, and like for
+/// any other `BufMut`, putting more bytes than that panics. Read or
+/// [`advance`](bytes::Buf::advance) past bytes to make room for more.
+#[cfg(feature = "bytes")]
+unsafe impl bytes::BufMut for AllocRingBuffer<u8> {
+    #[inline]
+    fn remaining_mut(&self) -> usize {
+        self.capacity - self.len()
+    }
+
+    fn chunk_mut(&mut self) -> &mut bytes::buf::UninitSlice {
+        // mask with and is allowed here because size is always a power of two
+        let start = mask_and(self.size, self.writeptr);
+        let len = self.remaining_mut().min(self.size - start);
+
+        // SAFETY: the `len` slots from the writeptr are free and don't wrap around
+        unsafe { bytes::buf::UninitSlice::from_raw_parts_mut(self.buf.add(start), len) }
+    }
+
+    unsafe fn advance_mut(&mut self, cnt: usize) {
+        assert!(
+            cnt <= self.remaining_mut(),
+            "cannot advance past the capacity of the ringbuffer"
+        );
+        let len = self.len();
+        self.writeptr += cnt;
+        self.total_pushed += cnt as u64;
+        self.update_high_water(len);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{AllocRingBuffer, OverwritePolicy, PushResult, RingBuffer, RingBufferError};
+
+    // just test that this compiles
+    #[test]
+    fn test_generic_clone() {
+        fn helper(a: &AllocRingBuffer<i32>) -> AllocRingBuffer<i32> {
+            a.clone()
+        }
+
+        _ = helper(&AllocRingBuffer::new(2));
+        _ = helper(&AllocRingBuffer::new(5));
+    }
+
+    #[test]
+    fn test_not_power_of_two() {
+        let mut rb = AllocRingBuffer::new(10);
+        const NUM_VALS: usize = 1000;
+
+        // recycle the ringbuffer a bunch of time to see if noneof the logic
+        // messes up
+        for _ in 0..100 {
+            for i in 0..NUM_VALS {
+                rb.enqueue(i);
+            }
+            assert!(rb.is_full());
+
+            for i in 0..10 {
+                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue());
+            }
+
+            assert!(rb.is_empty());
+        }
+    }
+
+    #[test]
+    fn test_with_capacity_power_of_two() {
+        let b = AllocRingBuffer::<i32>::with_capacity_power_of_2(2);
+        assert_eq!(b.capacity, 4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be greater than 0")]
+    fn test_zero_capacity() {
+        let _ = AllocRingBuffer::<i32>::new(0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert_eq!(
+            AllocRingBuffer::<i32>::try_new(0),
+            Err(RingBufferError::InvalidCapacity)
+        );
+        assert_eq!(AllocRingBuffer::<i32>::try_new(3).unwrap().capacity(), 3);
+    }
+
+    #[test]
+    fn test_zero_sized_type() {
+        let mut rb = AllocRingBuffer::<()>::try_new(3).unwrap();
+        rb.extend([(), (), (), ()]);
+        assert_eq!(rb.len(), 3);
+        assert_eq!(rb.dequeue(), Some(()));
+        assert_eq!(rb.clone().len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be greater than 0")]
+    fn test_zero_capacity_from_empty() {
+        let _ = AllocRingBuffer::<i32>::from(alloc::vec::Vec::new());
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn test_bytes_buf() {
+        use bytes::{Buf, BufMut};
+
+        let mut rb = AllocRingBuffer::new(6);
+        assert_eq!(rb.remaining_mut(), 6);
+
+        rb.put_slice(b"abcdef");
+        assert_eq!(rb.to_vec(), b"abcdef");
+        assert_eq!(rb.remaining(), 6);
+        assert_eq!(rb.remaining_mut(), 0);
+        assert_eq!(rb.get_u16(), u16::from_be_bytes(*b"ab"));
+        rb.advance(2);
+
+        // wraps around the end of the 8 byte allocation
+        rb.put_slice(b"ghij");
+        assert_eq!(rb.remaining_mut(), 0);
+        assert_eq!(rb.to_vec(), b"efghij");
+        assert_eq!(rb.total_pushed(), 10);
+
+        // chunk only returns the bytes up to the end of the allocation
+        assert_eq!(rb.chunk(), b"efgh");
+        // RingBuffer::copy_to_slice takes &self, so it is found first
+        let mut out = [0; 6];
+        Buf::copy_to_slice(&mut rb, &mut out);
+        assert_eq!(&out, b"efghij");
+        assert!(rb.is_empty());
+        assert!(rb.chunk().is_empty());
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    #[should_panic]
+    fn test_bytes_buf_mut_doesnt_overwrite() {
+        use bytes::BufMut;
+
+        let mut rb = AllocRingBuffer::new(2);
+        rb.put_slice(b"abc");
+    }
+
+    #[test]
+    fn test_capacity_is_exact() {
+        for (cap, size) in [(1, 1), (3, 4), (4, 4), (100, 128), (129, 256)] {
+            let mut b = AllocRingBuffer::<i32>::new(cap);
+            assert_eq!(b.capacity(), cap);
+            assert_eq!(b.buffer_size(), size);
+
+            // the buffer is full after exactly `cap` elements, not `size`
+            b.extend(0..cap as i32);
+            assert!(b.is_full());
+            b.push(-1);
+            assert_eq!(b.len(), cap);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_index_zero_length() {
+        let b = AllocRingBuffer::<i32>::new(2);
+        let _ = b[2];
     }
 
     #[test]
@@ -469,4 +1746,734 @@ mod tests {
         assert_eq!(buf.capacity, 4);
         assert_eq!(buf.to_vec(), alloc::vec![1, 2, 3, 4]);
     }
+
+    #[test]
+    fn test_into_vec_deque() {
+        #[derive(Debug, PartialEq)]
+        struct NoClone(i32);
+
+        let mut rb = AllocRingBuffer::new(3);
+        rb.extend((0..5).map(NoClone));
+
+        let deque = alloc::collections::VecDeque::from(rb);
+        assert_eq!(
+            deque.into_iter().collect::<alloc::vec::Vec<_>>(),
+            [NoClone(2), NoClone(3), NoClone(4)]
+        );
+    }
+
+    #[test]
+    fn test_vec_deque_round_trip() {
+        use alloc::collections::VecDeque;
+
+        // wrapped around the end of the allocation
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend(0..6);
+
+        let deque = VecDeque::from(&rb);
+        assert_eq!(deque, [2, 3, 4, 5]);
+        assert_eq!(VecDeque::from(rb.clone()), deque);
+
+        let mut back = AllocRingBuffer::from(deque);
+        assert_eq!(back, rb);
+        assert!(back.is_full());
+        back.push(6);
+        assert_eq!(back, [3, 4, 5, 6]);
+
+        // a deque whose elements wrap around its own storage
+        let mut deque = VecDeque::with_capacity(4);
+        deque.extend([1, 2, 3]);
+        let _ = deque.pop_front();
+        deque.extend([4, 5]);
+        assert_eq!(VecDeque::from(AllocRingBuffer::from(deque.clone())), deque);
+    }
+
+    #[test]
+    fn test_into_slices() {
+        // not wrapped
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend([1, 2, 3]);
+        let (a, b) = rb.into_slices();
+        assert_eq!(&*a, &[1, 2, 3]);
+        assert!(b.is_empty());
+
+        // wrapped
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend([1, 2, 3, 4, 5, 6]);
+        let (a, b) = rb.into_slices();
+        assert_eq!(&*a, &[3, 4]);
+        assert_eq!(&*b, &[5, 6]);
+
+        // capacity not a power of two, so the allocation is larger than the capacity
+        let mut rb = AllocRingBuffer::new(3);
+        rb.extend([1, 2, 3, 4, 5]);
+        let (a, b) = rb.into_slices();
+        assert_eq!(&*a, &[3, 4]);
+        assert_eq!(&*b, &[5]);
+
+        let rb = AllocRingBuffer::<i32>::new(4);
+        let (a, b) = rb.into_slices();
+        assert!(a.is_empty());
+        assert!(b.is_empty());
+    }
+
+    #[test]
+    fn test_into_chunks() {
+        let mut rb = AllocRingBuffer::new(5);
+        rb.extend([0, 1, 2, 3, 4, 5, 6]);
+
+        let mut chunks = rb.into_chunks(2);
+        assert_eq!(chunks.next(), Some(alloc::vec![2, 3]));
+        assert_eq!(chunks.next(), Some(alloc::vec![4, 5]));
+        assert_eq!(chunks.next(), Some(alloc::vec![6]));
+        assert_eq!(chunks.next(), None);
+
+        let rb = AllocRingBuffer::<i32>::new(4);
+        assert_eq!(rb.into_chunks(3).count(), 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_into_chunks_zero_size() {
+        let _ = AllocRingBuffer::from([1, 2]).into_chunks(0);
+    }
+
+    #[test]
+    fn test_rolling_hash() {
+        // "Wikipedia" is the example used for Adler-32
+        let rb = AllocRingBuffer::from(*b"Wikipedia");
+        assert_eq!(rb.rolling_hash(), 0x11E6_0398);
+
+        let rb = AllocRingBuffer::<u8>::new(4);
+        assert_eq!(rb.rolling_hash(), 1);
+    }
+
+    #[test]
+    fn test_roll_matches_recompute() {
+        let mut rb = AllocRingBuffer::new(16);
+        let mut hasher = rb.rolling_hasher();
+
+        let data = (0..1000u32).map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8);
+        for (n, byte) in data.enumerate() {
+            let expected_evicted = if hasher.buffer().is_full() {
+                hasher.buffer().peek().copied()
+            } else {
+                None
+            };
+
+            let (evicted, hash) = hasher.roll(byte);
+
+            assert_eq!(evicted, expected_evicted.unwrap_or(0));
+            assert_eq!(
+                hash,
+                hasher.buffer().rolling_hash(),
+                "mismatch after {} bytes",
+                n + 1
+            );
+            assert_eq!(hash, hasher.hash());
+        }
+
+        // all bytes at the maximum value make sure the modulus is handled
+        let mut rb = AllocRingBuffer::new(5000);
+        let mut hasher = rb.rolling_hasher();
+        for _ in 0..6000 {
+            let _ = hasher.roll(u8::MAX);
+        }
+        assert_eq!(hasher.hash(), rb.rolling_hash());
+    }
+
+    #[test]
+    fn test_roll_after_changes() {
+        let mut rb = AllocRingBuffer::from(*b"abcd");
+        let _ = rb.rolling_hasher().roll(b'e');
+
+        // a new hasher starts from the current contents, including bytes changed in place
+        rb.push(b'f');
+        rb[0] = b'x';
+        assert_eq!(
+            rb.rolling_hasher().roll(b'g'),
+            (b'x', AllocRingBuffer::from(*b"defg").rolling_hash())
+        );
+
+        let _ = rb.dequeue();
+        assert_eq!(
+            rb.rolling_hasher().roll(b'h'),
+            (0, AllocRingBuffer::from(*b"efgh").rolling_hash())
+        );
+    }
+
+    #[test]
+    fn test_overwrite_count() {
+        let mut rb = AllocRingBuffer::new(3);
+        rb.extend([1, 2, 3]);
+        // filling empty slots doesn't overwrite anything
+        assert_eq!(rb.overwrite_count(), 0);
+
+        rb.extend([4, 5]);
+        assert_eq!(rb.overwrite_count(), 2);
+
+        let _ = rb.dequeue();
+        rb.push(6);
+        assert_eq!(rb.overwrite_count(), 2);
+        assert_eq!(rb.clone().overwrite_count(), 2);
+
+        rb.reset_overwrite_count();
+        assert_eq!(rb.overwrite_count(), 0);
+        rb.push(7);
+        assert_eq!(rb.overwrite_count(), 1);
+        assert_eq!(rb.to_vec(), [5, 6, 7]);
+    }
+
+    #[test]
+    fn test_extend_from_slice_counters() {
+        let mut rb = AllocRingBuffer::new(3);
+        rb.extend_from_slice(&[1, 2]);
+        assert_eq!((rb.total_pushed(), rb.overwrite_count()), (2, 0));
+
+        rb.extend_from_slice(&[3, 4, 5, 6, 7, 8]);
+        assert_eq!((rb.total_pushed(), rb.overwrite_count()), (8, 5));
+        assert_eq!(rb.to_vec(), [6, 7, 8]);
+    }
+
+    #[test]
+    fn test_split_half() {
+        let mut rb = AllocRingBuffer::new(5);
+        rb.extend(0..7);
+
+        let (a, b) = rb.split_half();
+        assert_eq!(a.to_vec(), [2, 3]);
+        assert_eq!(b.to_vec(), [4, 5, 6]);
+        assert_eq!(a.capacity(), 5);
+        assert_eq!(b.capacity(), 5);
+
+        let (a, b) = AllocRingBuffer::from([1]).split_half();
+        assert!(a.is_empty());
+        assert_eq!(b.to_vec(), [1]);
+    }
+
+    #[test]
+    fn test_from_iter_lossy() {
+        let mut dropped = alloc::vec::Vec::new();
+        let rb = AllocRingBuffer::from_iter_lossy(4, 0..10, |i| dropped.push(i));
+
+        assert_eq!(rb.to_vec(), [6, 7, 8, 9]);
+        assert_eq!(dropped, [0, 1, 2, 3, 4, 5]);
+        assert_eq!(rb.overwrite_count(), 0);
+
+        let mut count = 0;
+        let rb = AllocRingBuffer::from_iter_lossy(4, 0..3, |_| count += 1);
+        assert_eq!(rb.to_vec(), [0, 1, 2]);
+        assert_eq!(count, 0);
+    }
+
+    #[test]
+    fn test_total_pushed() {
+        let mut rb = AllocRingBuffer::new(3);
+        assert_eq!(rb.total_pushed(), 0);
+
+        rb.extend(0..10);
+        assert_eq!(rb.total_pushed(), 10);
+
+        let _ = rb.dequeue();
+        rb.clear();
+        assert_eq!(rb.total_pushed(), 10);
+
+        rb.push(10);
+        assert_eq!(rb.total_pushed(), 11);
+        assert_eq!(rb.clone().total_pushed(), 11);
+    }
+
+    #[test]
+    fn test_total_pushed_reorganizing() {
+        // not full, so rotating requeues the elements
+        let mut rb = AllocRingBuffer::new(8);
+        rb.extend([1, 1, 2, 3]);
+        rb.rotate_left(1);
+        rb.rotate_right(2);
+        assert_eq!(rb.total_pushed(), 4);
+
+        rb.dedup();
+        rb.retain(|&i| i != 2);
+        rb.retain_newest(8, |_| true);
+        assert_eq!(rb.total_pushed(), 4);
+
+        // the inserted element is the only one pushed
+        assert_eq!(rb.insert(1, 4), Ok(()));
+        assert_eq!(rb.total_pushed(), 5);
+        assert_eq!(
+            rb.iter_enumerated()
+                .map(|(n, _)| n)
+                .collect::<alloc::vec::Vec<_>>(),
+            [2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn test_set_capacity() {
+        use alloc::rc::Rc;
+
+        let counter = Rc::new(());
+        // wraps around the end of the allocation
+        let mut rb = AllocRingBuffer::new(4);
+        rb.extend((0..6).map(|i| (i, Rc::clone(&counter))));
+        assert!(!rb.is_contiguous());
+        assert_eq!(Rc::strong_count(&counter), 5);
+
+        // growing keeps every element
+        rb.set_capacity(7);
+        assert_eq!((rb.capacity(), rb.buffer_size()), (7, 8));
+        assert_eq!(
+            rb.iter().map(|(i, _)| *i).collect::<alloc::vec::Vec<_>>(),
+            [2, 3, 4, 5]
+        );
+        rb.extend((6..10).map(|i| (i, Rc::clone(&counter))));
+        assert_eq!(rb.len(), 7);
+        assert_eq!(Rc::strong_count(&counter), 8);
+
+        // shrinking drops the oldest elements
+        rb.set_capacity(3);
+        assert_eq!((rb.capacity(), rb.buffer_size()), (3, 4));
+        assert_eq!(
+            rb.iter().map(|(i, _)| *i).collect::<alloc::vec::Vec<_>>(),
+            [7, 8, 9]
+        );
+        assert_eq!(Rc::strong_count(&counter), 4);
+        rb.push((10, Rc::clone(&counter)));
+        assert_eq!(
+            rb.iter().map(|(i, _)| *i).collect::<alloc::vec::Vec<_>>(),
+            [8, 9, 10]
+        );
+
+        // without changing the allocation
+        rb.set_capacity(4);
+        rb.push((11, Rc::clone(&counter)));
+        rb.set_capacity(3);
+        assert_eq!((rb.capacity(), rb.buffer_size()), (3, 4));
+        assert_eq!(
+            rb.iter().map(|(i, _)| *i).collect::<alloc::vec::Vec<_>>(),
+            [9, 10, 11]
+        );
+        assert_eq!(Rc::strong_count(&counter), 4);
+        assert_eq!(rb.total_pushed(), 12);
+
+        drop(rb);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    fn test_raw_writes_and_reads() {
+        use alloc::rc::Rc;
+
+        let counter = Rc::new(());
+        // a capacity of 3 in an allocation of 4 slots
+        let mut rb = AllocRingBuffer::new(3);
+        assert_eq!((rb.read_index(), rb.write_index()), (0, 0));
+        rb.extend((0..3).map(|i| (i, Rc::clone(&counter))));
+        let _ = rb.skip_n(2);
+        assert_eq!((rb.read_index(), rb.write_index()), (2, 3));
+
+        let start = rb.write_index();
+        let ptr = rb.as_mut_ptr();
+        for (offset, i) in [3, 4].into_iter().enumerate() {
+            unsafe {
+                ptr.add((start + offset) % 4)
+                    .write((i, Rc::clone(&counter)));
+            }
+        }
+        unsafe { rb.advance_write(2) };
+
+        assert!(rb.is_full());
+        assert_eq!(
+            rb.iter().map(|(i, _)| *i).collect::<alloc::vec::Vec<_>>(),
+            [2, 3, 4]
+        );
+        assert_eq!(rb.total_pushed(), 5);
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        let (first, _) = unsafe { rb.as_ptr().add(rb.read_index()).read() };
+        rb.advance_read(1);
+        assert_eq!(first, 2);
+        assert_eq!(rb.len(), 2);
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(rb);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_advance_write_past_capacity() {
+        let mut rb = AllocRingBuffer::<i32>::new(3);
+        rb.push(1);
+        // the allocation has room for 4 elements, but the capacity is 3
+        unsafe { rb.advance_write(3) };
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_advance_read_past_len() {
+        let mut rb = AllocRingBuffer::from([1, 2, 3, 4]);
+        rb.advance_read(5);
+    }
+
+    #[test]
+    fn test_filled() {
+        let mut rb = AllocRingBuffer::filled(5, 7);
+        assert!(rb.is_full());
+        assert_eq!(rb.len(), 5);
+        assert_eq!(rb.capacity(), 5);
+        assert_eq!(rb, [7; 5]);
+        assert_eq!(rb.overwrite_count(), 0);
+
+        for i in 0..5 {
+            rb.push(i);
+            assert_eq!(rb.len(), 5);
+        }
+        assert_eq!(rb, [0, 1, 2, 3, 4]);
+        assert_eq!(rb.overwrite_count(), 5);
+    }
+
+    #[test]
+    fn test_high_water() {
+        let mut rb = AllocRingBuffer::new(4);
+        rb.push(1);
+        assert!(!rb.high_water_reached());
+
+        rb.set_high_water(3);
+        rb.extend([2, 3, 4, 5]);
+        assert!(rb.high_water_reached());
+
+        // only crossing the threshold sets the flag again after a reset
+        rb.reset_high_water();
+        rb.push(6);
+        assert!(!rb.high_water_reached());
+
+        rb.clear();
+        assert!(!rb.high_water_reached());
+        rb.extend_from_slice(&[1, 2, 3]);
+        assert!(rb.high_water_reached());
+
+        // dropping below the threshold clears the flag, until a push crosses it again
+        let _ = rb.dequeue();
+        assert!(!rb.high_water_reached());
+        unsafe { rb.advance_write(0) };
+        rb.push(4);
+        assert!(rb.high_water_reached());
+        assert!(rb.clone().high_water_reached());
+
+        rb.set_high_water(2);
+        assert!(!rb.high_water_reached());
+    }
+
+    #[test]
+    fn test_high_water_fill() {
+        let mut rb = AllocRingBuffer::new(4);
+        rb.set_high_water(3);
+        rb.push(1);
+        rb.fill(0);
+        assert!(rb.high_water_reached());
+
+        rb.reset_high_water();
+        rb.fill_default();
+        assert!(rb.high_water_reached());
+    }
+
+    #[test]
+    #[cfg(feature = "bytes")]
+    fn test_high_water_bytes() {
+        use bytes::BufMut;
+
+        let mut rb = AllocRingBuffer::new(8);
+        rb.set_high_water(4);
+        rb.put_slice(b"abc");
+        assert!(!rb.high_water_reached());
+        rb.put_slice(b"de");
+        assert!(rb.high_water_reached());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_high_water_zero() {
+        AllocRingBuffer::<i32>::new(4).set_high_water(0);
+    }
+
+    #[test]
+    fn test_concat() {
+        use alloc::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut a = AllocRingBuffer::new(2);
+        a.extend((0..5).map(|i| (i, Rc::clone(&counter))));
+        let b = AllocRingBuffer::<(i32, Rc<()>)>::new(4);
+        let mut c = AllocRingBuffer::new(3);
+        c.extend((5..8).map(|i| (i, Rc::clone(&counter))));
+
+        let rb = AllocRingBuffer::concat([a, b, c]);
+        assert_eq!(rb.capacity(), 5);
+        assert!(rb.is_full());
+        assert_eq!(
+            rb.iter().map(|(i, _)| *i).collect::<alloc::vec::Vec<_>>(),
+            [3, 4, 5, 6, 7]
+        );
+        assert_eq!(Rc::strong_count(&counter), 6);
+
+        drop(rb);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let rb = AllocRingBuffer::<i32>::concat([]);
+        assert!(rb.is_empty());
+        assert_eq!(rb.capacity(), 1);
+    }
+
+    #[test]
+    fn test_try_reserve() {
+        let mut rb = AllocRingBuffer::new(5);
+        rb.set_policy(OverwritePolicy::Reject);
+        rb.extend(0..5);
+        let _ = rb.dequeue();
+        assert_eq!(rb.to_vec(), [1, 2, 3, 4]);
+        assert_eq!((rb.capacity(), rb.buffer_size()), (5, 8));
+
+        // enough free slots already
+        rb.try_reserve(1).unwrap();
+        assert_eq!(rb.capacity(), 5);
+
+        // grows within the allocation
+        let ptr = rb.as_ptr();
+        rb.try_reserve(3).unwrap();
+        assert_eq!((rb.capacity(), rb.buffer_size()), (7, 8));
+        assert_eq!(rb.as_ptr(), ptr);
+        assert_eq!(rb.to_vec(), [1, 2, 3, 4]);
+        rb.extend([5, 6, 7]);
+        assert_eq!(rb.to_vec(), [1, 2, 3, 4, 5, 6, 7]);
+        rb.try_reserve(0).unwrap();
+        assert_eq!(rb.capacity(), 7);
+
+        // grows the allocation, keeping the elements, counters and settings
+        rb.try_reserve(2).unwrap();
+        assert_eq!((rb.capacity(), rb.buffer_size()), (9, 16));
+        assert_eq!(rb.to_vec(), [1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(rb.total_pushed(), 8);
+        assert_eq!(rb.policy(), OverwritePolicy::Reject);
+
+        // the ringbuffer is unchanged when growing fails
+        assert_eq!(
+            rb.try_reserve(usize::MAX - 1),
+            Err(RingBufferError::CapacityOverflow)
+        );
+        assert_eq!(
+            rb.try_reserve(usize::MAX / 2),
+            Err(RingBufferError::CapacityOverflow)
+        );
+        assert_eq!(rb.capacity(), 9);
+        assert_eq!(rb.to_vec(), [1, 2, 3, 4, 5, 6, 7]);
+
+        assert_eq!(
+            AllocRingBuffer::<u64>::try_new(usize::MAX / 4).unwrap_err(),
+            RingBufferError::CapacityOverflow
+        );
+    }
+
+    #[test]
+    fn test_default_and_builder() {
+        let rb = AllocRingBuffer::<i32>::default();
+        assert_eq!(rb.capacity(), AllocRingBuffer::<i32>::DEFAULT_CAPACITY);
+        assert_eq!(rb.capacity(), 1024);
+        assert!(rb.is_empty());
+
+        #[derive(Default)]
+        struct Holder {
+            rb: AllocRingBuffer<u8>,
+        }
+        assert_eq!(Holder::default().rb.capacity(), 1024);
+
+        let rb = AllocRingBuffer::<i32>::builder().capacity(5).build();
+        assert_eq!((rb.capacity(), rb.buffer_size()), (5, 8));
+        assert_eq!(AllocRingBuffer::<i32>::builder().build().capacity(), 1024);
+        assert_eq!(
+            AllocRingBuffer::<i32>::builder().capacity(0).try_build(),
+            Err(RingBufferError::InvalidCapacity)
+        );
+    }
+
+    #[test]
+    fn test_overwrite_policy() {
+        let mut rb = AllocRingBuffer::builder()
+            .capacity(3)
+            .policy(OverwritePolicy::Reject)
+            .build();
+        assert_eq!(rb.policy(), OverwritePolicy::Reject);
+
+        rb.extend(0..3);
+        assert_eq!(rb.push_with_policy(3), Err(3));
+        assert_eq!(rb.to_vec(), [0, 1, 2]);
+        assert_eq!((rb.total_pushed(), rb.overwrite_count()), (3, 0));
+
+        let _ = rb.dequeue();
+        assert_eq!(rb.push_with_policy(3), Ok(PushResult::Inserted));
+        assert_eq!(rb.to_vec(), [1, 2, 3]);
+
+        // the other methods ignore the policy
+        rb.push(4);
+        rb.extend_from_slice(&[5]);
+        assert_eq!(rb.to_vec(), [3, 4, 5]);
+        assert_eq!(rb.try_push(6), Err(6));
+
+        // the policy is kept by operations which create new ringbuffers
+        assert_eq!(rb.clone().policy(), OverwritePolicy::Reject);
+        rb.set_capacity(5);
+        assert_eq!(rb.policy(), OverwritePolicy::Reject);
+        let mut tail = rb.split_off(1);
+        assert_eq!(tail.policy(), OverwritePolicy::Reject);
+        tail.extend(6..9);
+        assert_eq!(tail.push_with_policy(9), Err(9));
+        assert_eq!(tail.to_vec(), [4, 5, 6, 7, 8]);
+
+        rb.set_policy(OverwritePolicy::Overwrite);
+        rb.extend(4..8);
+        assert_eq!(rb.push_with_policy(8), Ok(PushResult::Overwrote(3)));
+        assert_eq!(rb.to_vec(), [4, 5, 6, 7, 8]);
+        assert_eq!(
+            AllocRingBuffer::<i32>::default().policy(),
+            OverwritePolicy::Overwrite
+        );
+    }
+
+    #[test]
+    fn test_overwrite_policy_try_push() {
+        // generic code only sees the rejection through try_push
+        fn push_all<RB: RingBuffer<i32>>(rb: &mut RB, items: &[i32]) -> alloc::vec::Vec<i32> {
+            items.iter().filter_map(|&i| rb.try_push(i).err()).collect()
+        }
+
+        let mut rb = AllocRingBuffer::builder()
+            .capacity(2)
+            .policy(OverwritePolicy::Reject)
+            .build();
+        assert_eq!(push_all(&mut rb, &[1, 2, 3, 4]), [3, 4]);
+        assert_eq!(rb.to_vec(), [1, 2]);
+    }
+
+    #[test]
+    fn test_iter_enumerated() {
+        let mut rb = AllocRingBuffer::new(3);
+        assert_eq!(rb.iter_enumerated().next(), None);
+
+        rb.extend(0..10);
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(7, &7), (8, &8), (9, &9)]
+        );
+
+        let _ = rb.dequeue();
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(8, &8), (9, &9)]
+        );
+
+        rb.clear();
+        rb.push(10);
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(10, &10)]
+        );
+    }
+
+    #[test]
+    fn test_iter_enumerated_after_fill() {
+        let mut rb = AllocRingBuffer::<i32>::new(4);
+        rb.fill(0);
+        assert_eq!(rb.total_pushed(), 4);
+        assert_eq!(
+            rb.iter_enumerated()
+                .map(|(n, _)| n)
+                .collect::<alloc::vec::Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+
+        rb.push(1);
+        rb.fill_default();
+        assert_eq!(rb.iter_enumerated().next(), Some((5, &0)));
+    }
+
+    #[test]
+    fn test_iter_enumerated_after_removing() {
+        let mut rb = AllocRingBuffer::new(8);
+        rb.extend(['a', 'b', 'c', 'd', 'e']);
+        // the survivors older than the removed element are renumbered
+        assert_eq!(rb.remove(2), Some('c'));
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(1, &'a'), (2, &'b'), (3, &'d'), (4, &'e')]
+        );
+
+        // removing from the front keeps the numbers
+        assert_eq!(rb.dequeue(), Some('a'));
+        assert_eq!(rb.iter_enumerated().next(), Some((2, &'b')));
+
+        // moving elements around doesn't count as pushing them
+        rb.extend(['e', 'f']);
+        rb.retain(|&c| c != 'b');
+        rb.dedup();
+        rb.rotate_left(1);
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(4, &'e'), (5, &'f'), (6, &'d')]
+        );
+    }
+
+    #[test]
+    fn test_clone_from() {
+        let mut source = AllocRingBuffer::new(3);
+        source.extend([1, 2, 3, 4]);
+
+        // a larger allocation is reused
+        let mut dest = AllocRingBuffer::from([9, 9, 9, 9, 9]);
+        let buf = dest.buf;
+        dest.clone_from(&source);
+        assert_eq!(dest.buf, buf);
+        assert_eq!(dest, source);
+        assert_eq!(dest.capacity(), 3);
+        assert_eq!(dest.to_vec(), [2, 3, 4]);
+
+        dest.push(5);
+        assert_eq!(dest.to_vec(), [3, 4, 5]);
+
+        // a smaller allocation has to grow
+        let mut source = AllocRingBuffer::new(10);
+        source.extend(0..10);
+        dest.clone_from(&source);
+        assert_eq!(dest, source);
+        assert_eq!(dest.capacity(), 10);
+    }
+
+    #[test]
+    fn test_clone_from_drops_leftovers() {
+        use alloc::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut dest = AllocRingBuffer::new(4);
+        dest.extend([Rc::clone(&counter), Rc::clone(&counter)]);
+
+        let source = AllocRingBuffer::from([Rc::new(())]);
+        dest.clone_from(&source);
+        assert_eq!(Rc::strong_count(&counter), 1);
+        assert_eq!(dest.len(), 1);
+    }
+
+    #[test]
+    fn test_from_exact_iter() {
+        let rb = AllocRingBuffer::from_exact_iter(4, [1, 2, 3, 4]).unwrap();
+        assert_eq!(rb.to_vec(), [1, 2, 3, 4]);
+        assert_eq!(rb.capacity(), 4);
+        assert!(rb.is_full());
+
+        // too few
+        assert_eq!(AllocRingBuffer::from_exact_iter(4, [1, 2, 3]), Err(3));
+        assert_eq!(AllocRingBuffer::<i32>::from_exact_iter(4, []), Err(0));
+
+        // too many
+        assert_eq!(AllocRingBuffer::from_exact_iter(4, 0..10), Err(10));
+        assert_eq!(AllocRingBuffer::from_exact_iter(1, 0..2), Err(2));
+    }
 }
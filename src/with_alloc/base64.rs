@@ -0,0 +1,192 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{self, Display, Formatter};
+
+use crate::{AllocRingBuffer, RingBuffer};
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const PAD: u8 = b'=';
+
+/// The error returned by [`AllocRingBuffer::from_base64`] when the input is not valid base64.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input decodes to zero bytes, and a ringbuffer can't have a capacity of zero.
+    Empty,
+    /// The length of the input is not a multiple of four.
+    InvalidLength,
+    /// The input contains a byte which is not part of the base64 alphabet at the given offset.
+    InvalidByte(usize, u8),
+}
+
+impl Display for DecodeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::Empty => write!(f, "input decodes to zero bytes"),
+            DecodeError::InvalidLength => write!(f, "input length is not a multiple of 4"),
+            DecodeError::InvalidByte(offset, byte) => {
+                write!(f, "invalid byte {:#04x} at offset {}", byte, offset)
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DecodeError {}
+
+fn decode_byte(offset: usize, byte: u8) -> Result<u32, DecodeError> {
+    let value = match byte {
+        b'A'..=b'Z' => byte - b'A',
+        b'a'..=b'z' => byte - b'a' + 26,
+        b'0'..=b'9' => byte - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return Err(DecodeError::InvalidByte(offset, byte)),
+    };
+
+    Ok(u32::from(value))
+}
+
+impl AllocRingBuffer<u8> {
+    /// Encodes the bytes in the ringbuffer, in dequeue order, as padded base64
+    /// using the standard alphabet.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = AllocRingBuffer::new(3);
+    /// rb.extend(*b"xman");
+    /// assert_eq!(rb.to_base64(), "bWFu");
+    /// ```
+    #[must_use]
+    pub fn to_base64(&self) -> String {
+        let bytes = self.to_vec();
+        let mut res = String::with_capacity((bytes.len() + 2) / 3 * 4);
+
+        for chunk in bytes.chunks(3) {
+            let group = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |acc, (i, &b)| acc | u32::from(b) << (16 - 8 * i));
+
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    let index = (group >> (18 - 6 * i)) & 0x3f;
+                    res.push(char::from(ALPHABET[index as usize]));
+                } else {
+                    res.push(char::from(PAD));
+                }
+            }
+        }
+
+        res
+    }
+
+    /// Decodes padded base64 in the standard alphabet into a new ringbuffer. The capacity
+    /// of the ringbuffer equals the number of decoded bytes, so the result is full.
+    ///
+    /// # Errors
+    /// Returns a [`DecodeError`] when the input is not valid base64, or when it decodes
+    /// to zero bytes.
+    ///
+    /// ```
+    /// use ringbuffer::{AllocRingBuffer, RingBuffer};
+    ///
+    /// let rb = AllocRingBuffer::from_base64("bWFu").unwrap();
+    /// assert_eq!(rb.to_vec(), b"man");
+    /// assert!(rb.is_full());
+    /// ```
+    pub fn from_base64(s: &str) -> Result<Self, DecodeError> {
+        let input = s.as_bytes();
+        if input.len() % 4 != 0 {
+            return Err(DecodeError::InvalidLength);
+        }
+
+        let mut bytes = Vec::with_capacity(input.len() / 4 * 3);
+        for (chunk_index, chunk) in input.chunks(4).enumerate() {
+            let offset = chunk_index * 4;
+            let is_last = offset + 4 == input.len();
+
+            // padding is only allowed at the end of the last chunk
+            let padding = if is_last {
+                chunk.iter().rev().take_while(|&&b| b == PAD).count()
+            } else {
+                0
+            };
+            if padding > 2 {
+                return Err(DecodeError::InvalidByte(offset + 4 - padding, PAD));
+            }
+
+            let mut group = 0;
+            for (i, &b) in chunk[..4 - padding].iter().enumerate() {
+                group |= decode_byte(offset + i, b)? << (18 - 6 * i);
+            }
+
+            for i in 0..3 - padding {
+                bytes.push((group >> (16 - 8 * i)) as u8);
+            }
+        }
+
+        if bytes.is_empty() {
+            return Err(DecodeError::Empty);
+        }
+
+        Ok(Self::from(bytes))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DecodeError;
+    use crate::{AllocRingBuffer, RingBuffer};
+
+    #[test]
+    fn test_encode() {
+        let cases: [(&[u8], &str); 6] = [
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ];
+
+        for (input, expected) in cases {
+            let rb = AllocRingBuffer::from(input);
+            assert_eq!(rb.to_base64(), expected);
+            assert_eq!(AllocRingBuffer::from_base64(expected).unwrap(), rb);
+        }
+
+        assert_eq!(AllocRingBuffer::<u8>::new(4).to_base64(), "");
+    }
+
+    #[test]
+    fn test_round_trip_wrapped() {
+        let mut rb = AllocRingBuffer::new(5);
+        rb.extend(0..=255);
+        assert_eq!(rb.to_vec(), [251, 252, 253, 254, 255]);
+
+        let decoded = AllocRingBuffer::from_base64(&rb.to_base64()).unwrap();
+        assert_eq!(decoded, rb);
+    }
+
+    #[test]
+    fn test_decode_errors() {
+        assert_eq!(AllocRingBuffer::from_base64(""), Err(DecodeError::Empty));
+        assert_eq!(
+            AllocRingBuffer::from_base64("Zm9"),
+            Err(DecodeError::InvalidLength)
+        );
+        assert_eq!(
+            AllocRingBuffer::from_base64("Zm9v!A=="),
+            Err(DecodeError::InvalidByte(4, b'!'))
+        );
+        assert_eq!(
+            AllocRingBuffer::from_base64("Zg==Zm9v"),
+            Err(DecodeError::InvalidByte(2, b'='))
+        );
+        assert_eq!(
+            AllocRingBuffer::from_base64("Z==="),
+            Err(DecodeError::InvalidByte(1, b'='))
+        );
+    }
+}
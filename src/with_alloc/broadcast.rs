@@ -0,0 +1,576 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug, Formatter};
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+use crate::{mask_and, RingBufferError};
+
+/// The `BroadcastRingBuffer` is a fixed size, single-producer multi-consumer ringbuffer in which
+/// every reader sees every element, like a broadcast channel. It needs the `sync` feature, like
+/// [`SpscRingBuffer`](crate::SpscRingBuffer).
+///
+/// The buffer itself only holds the storage. To use it, [`split`](BroadcastRingBuffer::split) it
+/// into a [`Broadcaster`], which pushes elements, and a first [`Reader`]. More readers are
+/// created with [`Broadcaster::subscribe`], or by cloning a reader. Every handle can be sent to a
+/// different thread.
+///
+/// Readers don't remove elements, they receive clones of them and advance their own cursor. The
+/// broadcaster never waits for slow readers: like [`RingBuffer::push`](crate::RingBuffer::push),
+/// pushing to a full buffer overwrites the oldest element. A reader which fell behind by more
+/// than the capacity misses the overwritten elements, and learns how many it missed through
+/// [`ReadError::Lagged`].
+///
+/// # Synchronization
+/// The buffer keeps one ever-increasing index, the `writeptr`, which only the broadcaster stores
+/// to. Each slot remembers the index of the element it holds, and is guarded by a tiny spinlock,
+/// so a reader never clones an element while it is being overwritten. The locks are only held
+/// for a single move or clone, so [`Broadcaster::push`] may spin briefly while a reader clones the
+/// element in the slot it is about to overwrite, but never waits for a reader to catch up.
+///
+/// # Example
+/// ```
+/// use ringbuffer::{BroadcastRingBuffer, ReadError};
+///
+/// let (mut broadcaster, mut a) = BroadcastRingBuffer::new(2).split();
+/// broadcaster.push(1);
+/// let mut b = broadcaster.subscribe();
+/// broadcaster.push(2);
+///
+/// // every reader sees the elements pushed since it was created
+/// assert_eq!(a.read(), Ok(1));
+/// assert_eq!(a.read(), Ok(2));
+/// assert_eq!(a.read(), Err(ReadError::Empty));
+///
+/// broadcaster.push(3);
+/// broadcaster.push(4);
+/// // 2 was overwritten before b read it
+/// assert_eq!(b.read(), Err(ReadError::Lagged(1)));
+/// assert_eq!(b.read(), Ok(3));
+/// assert_eq!(b.read(), Ok(4));
+/// ```
+pub struct BroadcastRingBuffer<T> {
+    slots: Box<[Slot<T>]>,
+
+    // how far a reader may fall behind before it misses elements.
+    // Always less than or equal than the number of slots, which is a power of two.
+    capacity: usize,
+
+    // index of the next slot to be written. Only ever written by the broadcaster.
+    writeptr: AtomicUsize,
+}
+
+struct Slot<T> {
+    locked: AtomicBool,
+    // the index of the element in value, if the slot was written yet. Only accessed while locked.
+    index: UnsafeCell<Option<usize>>,
+    value: UnsafeCell<MaybeUninit<T>>,
+}
+
+impl<T> Slot<T> {
+    // the lock is released when the returned guard is dropped, also when cloning the
+    // element panics.
+    fn lock(&self) -> SlotGuard<'_, T> {
+        while self
+            .locked
+            .compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed)
+            .is_err()
+        {
+            core::hint::spin_loop();
+        }
+        SlotGuard { slot: self }
+    }
+
+    fn unlock(&self) {
+        self.locked.store(false, Ordering::Release);
+    }
+}
+
+struct SlotGuard<'a, T> {
+    slot: &'a Slot<T>,
+}
+
+impl<T> Drop for SlotGuard<'_, T> {
+    fn drop(&mut self) {
+        self.slot.unlock();
+    }
+}
+
+// SAFETY: the slots are only accessed while their lock is held, and the elements are moved in by
+// the broadcaster and cloned out by the readers, possibly on other threads, so T must be Send.
+unsafe impl<T: Send> Sync for BroadcastRingBuffer<T> {}
+unsafe impl<T: Send> Send for BroadcastRingBuffer<T> {}
+
+impl<T> BroadcastRingBuffer<T> {
+    /// Creates a `BroadcastRingBuffer` with a certain capacity. The capacity must not be zero.
+    ///
+    /// # Panics
+    /// Panics when capacity is zero or too large. Use [`try_new`](BroadcastRingBuffer::try_new) to
+    /// handle those cases instead.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        match Self::try_new(capacity) {
+            Ok(rb) => rb,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Creates a `BroadcastRingBuffer` with a certain capacity, like
+    /// [`new`](BroadcastRingBuffer::new).
+    ///
+    /// # Errors
+    /// Returns [`RingBufferError::InvalidCapacity`] when capacity is zero, and
+    /// [`RingBufferError::CapacityOverflow`] when it is too large to round up to a power of two.
+    pub fn try_new(capacity: usize) -> Result<Self, RingBufferError> {
+        if capacity == 0 {
+            return Err(RingBufferError::InvalidCapacity);
+        }
+        let size = capacity
+            .checked_next_power_of_two()
+            .ok_or(RingBufferError::CapacityOverflow)?;
+
+        Ok(Self {
+            slots: (0..size)
+                .map(|_| Slot {
+                    locked: AtomicBool::new(false),
+                    index: UnsafeCell::new(None),
+                    value: UnsafeCell::new(MaybeUninit::uninit()),
+                })
+                .collect(),
+            capacity,
+            writeptr: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the capacity of the buffer: the number of elements a reader can fall behind
+    /// before it misses some.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Splits the buffer into a [`Broadcaster`] and a [`Reader`], which can be used from
+    /// different threads at the same time. The reader starts at the first element pushed.
+    #[must_use]
+    pub fn split(self) -> (Broadcaster<T>, Reader<T>) {
+        let shared = Arc::new(self);
+        (
+            Broadcaster {
+                rb: Arc::clone(&shared),
+            },
+            Reader {
+                rb: shared,
+                readptr: 0,
+            },
+        )
+    }
+
+    #[inline]
+    fn slot(&self, index: usize) -> &Slot<T> {
+        // mask with and is allowed here because the number of slots is always a power of two
+        &self.slots[mask_and(self.slots.len(), index)]
+    }
+}
+
+impl<T> Drop for BroadcastRingBuffer<T> {
+    fn drop(&mut self) {
+        for slot in self.slots.iter_mut() {
+            if slot.index.get_mut().is_some() {
+                // Safety: the slot was written, and we have exclusive access to the buffer
+                unsafe { slot.value.get_mut().as_mut_ptr().drop_in_place() };
+            }
+        }
+    }
+}
+
+impl<T> Debug for BroadcastRingBuffer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BroadcastRingBuffer")
+            .field("capacity", &self.capacity)
+            .field("writeptr", &self.writeptr.load(Ordering::Acquire))
+            .finish()
+    }
+}
+
+/// The writing half of a [`BroadcastRingBuffer`], created by [`BroadcastRingBuffer::split`].
+pub struct Broadcaster<T> {
+    rb: Arc<BroadcastRingBuffer<T>>,
+}
+
+impl<T> Broadcaster<T> {
+    /// Pushes a value onto the buffer, overwriting the oldest element when the buffer is full.
+    /// Readers which haven't read the overwritten element yet miss it.
+    pub fn push(&mut self, value: T) {
+        // only this broadcaster writes the writeptr, so no synchronization is needed to read it
+        let writeptr = self.rb.writeptr.load(Ordering::Relaxed);
+        let slot = self.rb.slot(writeptr);
+
+        let guard = slot.lock();
+        // Safety: the slot is locked, and it only holds an initialized element if it has an index
+        let old = unsafe {
+            let old = (*slot.index.get())
+                .replace(writeptr)
+                .map(|_| core::ptr::read((*slot.value.get()).as_ptr()));
+            let _ = (*slot.value.get()).write(value);
+            old
+        };
+        drop(guard);
+
+        // release: publish the written slot to the readers
+        self.rb
+            .writeptr
+            .store(writeptr.wrapping_add(1), Ordering::Release);
+
+        // drop the overwritten element outside of the lock
+        drop(old);
+    }
+
+    /// Creates a new [`Reader`], which reads the elements pushed from now on.
+    #[must_use]
+    pub fn subscribe(&self) -> Reader<T> {
+        Reader {
+            rb: Arc::clone(&self.rb),
+            readptr: self.rb.writeptr.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Returns the capacity of the buffer.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.rb.capacity()
+    }
+}
+
+impl<T> Debug for Broadcaster<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Broadcaster").field("rb", &self.rb).finish()
+    }
+}
+
+/// A reading handle of a [`BroadcastRingBuffer`], created by [`BroadcastRingBuffer::split`],
+/// [`Broadcaster::subscribe`], or by cloning another reader. Every reader has its own cursor, and
+/// receives a clone of every element pushed after it was created.
+pub struct Reader<T> {
+    rb: Arc<BroadcastRingBuffer<T>>,
+    // index of the next element to be read
+    readptr: usize,
+}
+
+impl<T: Clone> Reader<T> {
+    /// Returns a clone of the next element and advances the cursor of this reader.
+    ///
+    /// # Errors
+    /// Returns [`ReadError::Empty`] when this reader has read every element pushed so far.
+    ///
+    /// Returns [`ReadError::Lagged`] with the number of missed elements, when elements this
+    /// reader hasn't read yet were overwritten. The cursor is moved to the oldest element still
+    /// in the buffer, so the next call continues from there.
+    pub fn read(&mut self) -> Result<T, ReadError> {
+        loop {
+            // acquire: the broadcaster must be done writing a slot before we may read it
+            let writeptr = self.rb.writeptr.load(Ordering::Acquire);
+            let behind = writeptr.wrapping_sub(self.readptr);
+
+            if behind == 0 {
+                return Err(ReadError::Empty);
+            }
+            if behind > self.rb.capacity {
+                let missed = behind - self.rb.capacity;
+                self.readptr = self.readptr.wrapping_add(missed);
+                return Err(ReadError::Lagged(missed));
+            }
+
+            let slot = self.rb.slot(self.readptr);
+            let guard = slot.lock();
+            // Safety: the slot is locked, and it holds an initialized element if it has an index
+            let value = unsafe {
+                match *slot.index.get() {
+                    Some(index) if index == self.readptr => {
+                        Some((*(*slot.value.get()).as_ptr()).clone())
+                    }
+                    _ => None,
+                }
+            };
+            drop(guard);
+
+            match value {
+                Some(value) => {
+                    self.readptr = self.readptr.wrapping_add(1);
+                    return Ok(value);
+                }
+                // the element was overwritten after we loaded the writeptr, so by now we
+                // are lagging behind. Load the writeptr again to find out by how much.
+                None => continue,
+            }
+        }
+    }
+}
+
+impl<T> Reader<T> {
+    /// Returns the number of elements this reader hasn't read yet, which can be more than the
+    /// capacity when it is lagging behind. The broadcaster may push concurrently, so the actual
+    /// number can be larger than the returned value.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rb
+            .writeptr
+            .load(Ordering::Acquire)
+            .wrapping_sub(self.readptr)
+    }
+
+    /// Returns true if this reader has read every element pushed so far.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the capacity of the buffer.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.rb.capacity()
+    }
+}
+
+/// Creates a reader with the same cursor, which reads the same elements from then on.
+impl<T> Clone for Reader<T> {
+    fn clone(&self) -> Self {
+        Self {
+            rb: Arc::clone(&self.rb),
+            readptr: self.readptr,
+        }
+    }
+}
+
+impl<T> Debug for Reader<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reader")
+            .field("rb", &self.rb)
+            .field("readptr", &self.readptr)
+            .finish()
+    }
+}
+
+/// The error returned by [`Reader::read`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadError {
+    /// The reader has read every element pushed so far.
+    Empty,
+    /// The reader fell behind by more than the capacity, and missed this many elements because
+    /// they were overwritten.
+    Lagged(usize),
+}
+
+impl fmt::Display for ReadError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ReadError::Empty => write!(f, "No new elements to read"),
+            ReadError::Lagged(n) => write!(f, "Reader lagged behind and missed {} elements", n),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ReadError {}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::{BroadcastRingBuffer, ReadError};
+    use crate::RingBufferError;
+    use std::thread;
+    use std::vec::Vec;
+
+    #[test]
+    fn test_readers_at_different_speeds() {
+        let (mut broadcaster, fast) = BroadcastRingBuffer::new(3).split();
+        let mut readers = [fast.clone(), fast.clone(), fast];
+        let mut received = [Vec::new(), Vec::new(), Vec::new()];
+        let mut lagged = [0, 0, 0];
+
+        for i in 0..30 {
+            broadcaster.push(i);
+            // reader k reads one element every k + 1 pushes
+            for (k, reader) in readers.iter_mut().enumerate() {
+                if i % (k + 1) == 0 {
+                    let v = reader.read().or_else(|e| match e {
+                        ReadError::Lagged(n) => {
+                            lagged[k] += n;
+                            reader.read()
+                        }
+                        ReadError::Empty => Err(e),
+                    });
+                    received[k].push(v.unwrap());
+                }
+            }
+        }
+
+        // the fast reader keeps up, the slower ones miss elements
+        assert_eq!(received[0], (0..30).collect::<Vec<_>>());
+        assert_eq!(received[1][..4], [0, 1, 2, 4]);
+        assert_eq!(received[2][..4], [0, 1, 4, 7]);
+        assert_eq!(lagged, [0, 12, 16]);
+
+        for (k, reader) in readers.iter_mut().enumerate() {
+            loop {
+                match reader.read() {
+                    Ok(v) => received[k].push(v),
+                    Err(ReadError::Lagged(n)) => lagged[k] += n,
+                    Err(ReadError::Empty) => break,
+                }
+            }
+            assert_eq!(reader.read(), Err(ReadError::Empty));
+            assert!(reader.is_empty());
+
+            // every element was either received, in order, or reported as missed
+            assert_eq!(received[k].len() + lagged[k], 30);
+            assert!(received[k].windows(2).all(|w| w[0] < w[1]));
+            assert_eq!(received[k][received[k].len() - 3..], [27, 28, 29]);
+        }
+    }
+
+    #[test]
+    fn test_subscribe() {
+        let (mut broadcaster, mut first) = BroadcastRingBuffer::new(4).split();
+        broadcaster.push(1);
+        broadcaster.push(2);
+
+        let mut late = broadcaster.subscribe();
+        assert!(late.is_empty());
+        broadcaster.push(3);
+
+        assert_eq!(first.len(), 3);
+        assert_eq!(first.read(), Ok(1));
+        assert_eq!(late.read(), Ok(3));
+        assert_eq!(late.read(), Err(ReadError::Empty));
+
+        // a clone continues from the same position
+        let mut copy = first.clone();
+        assert_eq!(first.read(), Ok(2));
+        assert_eq!(copy.read(), Ok(2));
+    }
+
+    #[test]
+    fn test_lagged_skips_to_oldest() {
+        // the capacity limits the lag, even though there are 8 slots
+        let (mut broadcaster, mut reader) = BroadcastRingBuffer::new(5).split();
+        for i in 0..7 {
+            broadcaster.push(i);
+        }
+        assert_eq!(reader.len(), 7);
+        assert_eq!(reader.read(), Err(ReadError::Lagged(2)));
+        assert_eq!(reader.read(), Ok(2));
+
+        for i in 7..100 {
+            broadcaster.push(i);
+        }
+        assert_eq!(reader.read(), Err(ReadError::Lagged(92)));
+        assert_eq!(reader.read(), Ok(95));
+    }
+
+    #[test]
+    fn test_drops_elements() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        let (mut broadcaster, mut reader) = BroadcastRingBuffer::new(2).split();
+        for _ in 0..5 {
+            broadcaster.push(Rc::clone(&counter));
+        }
+        // the overwritten elements are dropped
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        assert_eq!(reader.read().map(drop), Err(ReadError::Lagged(3)));
+        drop(reader.read());
+        assert_eq!(Rc::strong_count(&counter), 3);
+
+        drop(broadcaster);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        drop(reader);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    // this test takes far too long with Miri enabled
+    fn test_one_broadcaster_three_reader_threads() {
+        const COUNT: usize = 200_000;
+
+        let (mut broadcaster, reader) = BroadcastRingBuffer::new(64).split();
+        let readers: Vec<_> = (0..3)
+            .map(|k| {
+                let mut reader = reader.clone();
+                thread::spawn(move || {
+                    let mut received = 0;
+                    let mut lagged = 0;
+                    let mut last = None;
+                    loop {
+                        match reader.read() {
+                            Ok(v) => {
+                                // the elements arrive in order, even when some are missed
+                                assert!(last.map_or(true, |last| last < v));
+                                last = Some(v);
+                                received += 1;
+                                if v == COUNT {
+                                    return (received, lagged);
+                                }
+                                // the readers run at different speeds
+                                for _ in 0..k * 50 {
+                                    core::hint::spin_loop();
+                                }
+                            }
+                            Err(ReadError::Lagged(n)) => lagged += n,
+                            Err(ReadError::Empty) => thread::yield_now(),
+                        }
+                    }
+                })
+            })
+            .collect();
+        drop(reader);
+
+        for i in 1..=COUNT {
+            broadcaster.push(i);
+        }
+
+        for t in readers {
+            let (received, lagged) = t.join().unwrap();
+            assert_eq!(received + lagged, COUNT);
+        }
+    }
+
+    #[test]
+    fn test_panicking_clone_releases_slot() {
+        struct PanicOnClone;
+
+        impl Clone for PanicOnClone {
+            fn clone(&self) -> Self {
+                panic!("clone failed");
+            }
+        }
+
+        let (mut broadcaster, mut reader) = BroadcastRingBuffer::new(1).split();
+        broadcaster.push(PanicOnClone);
+        let result = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| reader.read()));
+        assert!(result.is_err());
+
+        // the slot was unlocked while unwinding, so it can be overwritten
+        broadcaster.push(PanicOnClone);
+        assert_eq!(reader.len(), 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be greater than 0")]
+    fn test_zero_capacity() {
+        let _ = BroadcastRingBuffer::<i32>::new(0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(BroadcastRingBuffer::<i32>::try_new(0).is_err());
+        assert_eq!(
+            BroadcastRingBuffer::<i32>::try_new(usize::MAX).err(),
+            Some(RingBufferError::CapacityOverflow)
+        );
+    }
+}
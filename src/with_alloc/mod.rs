@@ -1,2 +1,8 @@
 pub mod alloc_ringbuffer;
+#[cfg(feature = "base64")]
+pub mod base64;
+#[cfg(feature = "sync")]
+pub mod broadcast;
+#[cfg(feature = "sync")]
+pub mod spsc;
 pub mod vecdeque;
@@ -0,0 +1,473 @@
+use alloc::boxed::Box;
+use alloc::sync::Arc;
+use core::cell::UnsafeCell;
+use core::fmt::{self, Debug, Formatter};
+use core::mem::MaybeUninit;
+use core::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{mask_and, RingBuffer, RingBufferError};
+
+/// The `SpscRingBuffer` is a fixed size, single-producer single-consumer ringbuffer which can be
+/// shared between two threads without locking. It needs the `sync` feature, since it relies on
+/// [`Arc`], which isn't available on targets without atomic compare-and-swap.
+///
+/// The buffer itself only holds the storage. To use it, [`split`](SpscRingBuffer::split) it into a
+/// [`Producer`], which pushes elements, and a [`Consumer`], which pops them. Both handles can be
+/// sent to different threads. Because both ends run concurrently, pushing to a full buffer does
+/// not overwrite the oldest element like [`RingBuffer::push`](crate::RingBuffer::push) does, but
+/// hands the element back instead.
+///
+/// This type deliberately does not implement [`RingBuffer`](crate::RingBuffer), since most of that
+/// API (indexing, iterating, mutating elements in the middle of the buffer) can't be offered
+/// safely while the other end is in use.
+///
+/// # Memory ordering
+/// The buffer keeps two ever-increasing indices: the `writeptr`, which only the producer stores
+/// to, and the `readptr`, which only the consumer stores to. Neither [`Producer::push`] nor
+/// [`Consumer::pop`] ever waits for the other side; both are wait-free and finish in a
+/// bounded number of steps.
+///
+/// * The producer writes the element into its slot *before* storing the new `writeptr` with
+///   `Release` ordering. The consumer loads the `writeptr` with `Acquire` ordering, so once it
+///   observes the new index the element is guaranteed to be fully written.
+/// * Symmetrically, the consumer moves the element out of its slot *before* storing the new
+///   `readptr` with `Release` ordering, and the producer loads it with `Acquire`. A slot is
+///   therefore never overwritten while it is still being read.
+/// * Each side loads its own index with `Relaxed` ordering, since no other thread writes it.
+///
+/// The storage is shared through an [`Arc`], so it is freed exactly once, when the last of
+/// the two handles is dropped. Elements still in the buffer at that point are dropped as well.
+///
+/// # Example
+/// ```
+/// use ringbuffer::SpscRingBuffer;
+///
+/// let (mut producer, mut consumer) = SpscRingBuffer::new(2).split();
+///
+/// let t = std::thread::spawn(move || {
+///     for i in 0..100 {
+///         let mut value = i;
+///         // spin until the consumer made room
+///         while let Err(v) = producer.push(value) {
+///             value = v;
+///             std::hint::spin_loop();
+///         }
+///     }
+/// });
+///
+/// let mut received = 0;
+/// while received < 100 {
+///     if let Some(v) = consumer.pop() {
+///         assert_eq!(v, received);
+///         received += 1;
+///     }
+/// }
+///
+/// t.join().unwrap();
+/// ```
+pub struct SpscRingBuffer<T> {
+    buf: Box<[UnsafeCell<MaybeUninit<T>>]>,
+
+    // maximum number of elements actually allowed in the ringbuffer.
+    // Always less than or equal than the size of buf, which is a power of two.
+    capacity: usize,
+
+    // index of the next element to be popped. Only ever written by the consumer.
+    readptr: AtomicUsize,
+    // index of the next slot to be written. Only ever written by the producer.
+    writeptr: AtomicUsize,
+}
+
+// SAFETY: the producer only writes slots which the consumer has released, and the consumer only
+// reads slots which the producer has published. The atomic pointers synchronize these accesses,
+// so sharing the buffer between the two threads is safe as long as T can be sent between them.
+unsafe impl<T: Send> Sync for SpscRingBuffer<T> {}
+unsafe impl<T: Send> Send for SpscRingBuffer<T> {}
+
+impl<T> SpscRingBuffer<T> {
+    /// Creates a `SpscRingBuffer` with a certain capacity. The capacity must not be zero.
+    ///
+    /// # Panics
+    /// Panics when capacity is zero or too large. Use [`try_new`](SpscRingBuffer::try_new) to
+    /// handle those cases instead.
+    #[must_use]
+    pub fn new(capacity: usize) -> Self {
+        match Self::try_new(capacity) {
+            Ok(rb) => rb,
+            Err(e) => panic!("{}", e),
+        }
+    }
+
+    /// Creates a `SpscRingBuffer` with a certain capacity, like [`new`](SpscRingBuffer::new).
+    ///
+    /// # Errors
+    /// Returns [`RingBufferError::InvalidCapacity`] when capacity is zero, and
+    /// [`RingBufferError::CapacityOverflow`] when it is too large to round up to a power of two.
+    pub fn try_new(capacity: usize) -> Result<Self, RingBufferError> {
+        if capacity == 0 {
+            return Err(RingBufferError::InvalidCapacity);
+        }
+        let size = capacity
+            .checked_next_power_of_two()
+            .ok_or(RingBufferError::CapacityOverflow)?;
+
+        Ok(Self {
+            buf: (0..size)
+                .map(|_| UnsafeCell::new(MaybeUninit::uninit()))
+                .collect(),
+            capacity,
+            readptr: AtomicUsize::new(0),
+            writeptr: AtomicUsize::new(0),
+        })
+    }
+
+    /// Returns the capacity of the buffer.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Returns the number of elements in the buffer. When the buffer is shared, the other end
+    /// may push or pop concurrently, so the result can already be outdated when it is returned.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let readptr = self.readptr.load(Ordering::Acquire);
+        let writeptr = self.writeptr.load(Ordering::Acquire);
+        writeptr.wrapping_sub(readptr)
+    }
+
+    /// Returns true if the buffer is entirely empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns true when the length of the buffer equals the capacity.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.len() == self.capacity
+    }
+
+    /// Splits the buffer into a [`Producer`] and a [`Consumer`], which can be used from two
+    /// different threads at the same time.
+    #[must_use]
+    pub fn split(self) -> (Producer<T>, Consumer<T>) {
+        let shared = Arc::new(self);
+        (
+            Producer {
+                rb: Arc::clone(&shared),
+            },
+            Consumer { rb: shared },
+        )
+    }
+
+    #[inline]
+    fn slot(&self, index: usize) -> *mut MaybeUninit<T> {
+        // mask with and is allowed here because the size of buf is always a power of two
+        self.buf[mask_and(self.buf.len(), index)].get()
+    }
+}
+
+impl<T> From<crate::AllocRingBuffer<T>> for SpscRingBuffer<T> {
+    fn from(mut value: crate::AllocRingBuffer<T>) -> Self {
+        let mut res = SpscRingBuffer::new(value.capacity());
+
+        let mut writeptr = 0;
+        for i in value.drain() {
+            // Safety: we have exclusive access, and never write more than capacity elements
+            unsafe { res.slot(writeptr).write(MaybeUninit::new(i)) };
+            writeptr += 1;
+        }
+        *res.writeptr.get_mut() = writeptr;
+
+        res
+    }
+}
+
+impl<T> Drop for SpscRingBuffer<T> {
+    fn drop(&mut self) {
+        let readptr = *self.readptr.get_mut();
+        let writeptr = *self.writeptr.get_mut();
+
+        let mut index = readptr;
+        while index != writeptr {
+            // Safety: all slots between readptr and writeptr are initialized, and we have
+            // exclusive access to the buffer.
+            unsafe { (*self.slot(index)).as_mut_ptr().drop_in_place() };
+            index = index.wrapping_add(1);
+        }
+    }
+}
+
+impl<T> Debug for SpscRingBuffer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpscRingBuffer")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// The writing half of a [`SpscRingBuffer`], created by [`SpscRingBuffer::split`].
+pub struct Producer<T> {
+    rb: Arc<SpscRingBuffer<T>>,
+}
+
+impl<T> Producer<T> {
+    /// Pushes a value onto the buffer. When the buffer is full, the value is handed back
+    /// in the `Err` variant and the buffer is left unchanged.
+    pub fn push(&mut self, value: T) -> Result<(), T> {
+        // only this producer writes the writeptr, so no synchronization is needed to read it
+        let writeptr = self.rb.writeptr.load(Ordering::Relaxed);
+        // acquire: the consumer must be done reading a slot before we may overwrite it
+        let readptr = self.rb.readptr.load(Ordering::Acquire);
+
+        if writeptr.wrapping_sub(readptr) == self.rb.capacity {
+            return Err(value);
+        }
+
+        // Safety: the slot is not between readptr and writeptr, so the consumer won't touch it
+        // until we publish it below.
+        unsafe { self.rb.slot(writeptr).write(MaybeUninit::new(value)) };
+
+        // release: publish the written slot to the consumer
+        self.rb
+            .writeptr
+            .store(writeptr.wrapping_add(1), Ordering::Release);
+
+        Ok(())
+    }
+
+    /// Returns the capacity of the buffer.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.rb.capacity()
+    }
+
+    /// Returns the number of elements in the buffer. The consumer may pop concurrently,
+    /// so the actual length can be smaller than the returned value.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rb.len()
+    }
+
+    /// Returns true if the buffer is entirely empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rb.is_empty()
+    }
+
+    /// Returns true when the buffer is full, and the next [`push`](Producer::push) would fail
+    /// unless the consumer pops an element first.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.rb.is_full()
+    }
+}
+
+impl<T> Debug for Producer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Producer").field("rb", &self.rb).finish()
+    }
+}
+
+/// The reading half of a [`SpscRingBuffer`], created by [`SpscRingBuffer::split`].
+pub struct Consumer<T> {
+    rb: Arc<SpscRingBuffer<T>>,
+}
+
+impl<T> Consumer<T> {
+    /// Pops the oldest element off the buffer and moves it out,
+    /// or returns `None` when the buffer is empty.
+    pub fn pop(&mut self) -> Option<T> {
+        // only this consumer writes the readptr, so no synchronization is needed to read it
+        let readptr = self.rb.readptr.load(Ordering::Relaxed);
+        // acquire: the producer must be done writing a slot before we may read it
+        let writeptr = self.rb.writeptr.load(Ordering::Acquire);
+
+        if readptr == writeptr {
+            return None;
+        }
+
+        // Safety: the slot is between readptr and writeptr, so it is initialized and the
+        // producer won't touch it until we release it below.
+        let value = unsafe { self.rb.slot(readptr).read().assume_init() };
+
+        // release: hand the slot back to the producer
+        self.rb
+            .readptr
+            .store(readptr.wrapping_add(1), Ordering::Release);
+
+        Some(value)
+    }
+
+    /// Returns the capacity of the buffer.
+    #[must_use]
+    pub fn capacity(&self) -> usize {
+        self.rb.capacity()
+    }
+
+    /// Returns the number of elements in the buffer. The producer may push concurrently,
+    /// so the actual length can be larger than the returned value.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.rb.len()
+    }
+
+    /// Returns true if the buffer is entirely empty, and the next [`pop`](Consumer::pop)
+    /// would return `None` unless the producer pushes an element first.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.rb.is_empty()
+    }
+
+    /// Returns true when the length of the buffer equals the capacity.
+    #[must_use]
+    pub fn is_full(&self) -> bool {
+        self.rb.is_full()
+    }
+}
+
+impl<T> Debug for Consumer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Consumer").field("rb", &self.rb).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::SpscRingBuffer;
+    use crate::{AllocRingBuffer, RingBufferError};
+    use std::thread;
+
+    #[test]
+    fn test_push_pop() {
+        let (mut producer, mut consumer) = SpscRingBuffer::new(3).split();
+        assert_eq!(producer.capacity(), 3);
+        assert!(consumer.is_empty());
+        assert_eq!(consumer.pop(), None);
+
+        assert_eq!(producer.push(1), Ok(()));
+        assert_eq!(producer.push(2), Ok(()));
+        assert_eq!(producer.push(3), Ok(()));
+        assert!(producer.is_full());
+        // a full buffer hands the value back instead of overwriting
+        assert_eq!(producer.push(4), Err(4));
+        assert_eq!(consumer.len(), 3);
+
+        assert_eq!(consumer.pop(), Some(1));
+        assert_eq!(producer.push(4), Ok(()));
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), Some(4));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_drops_remaining_elements() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+        {
+            let rb = SpscRingBuffer::new(4);
+            let (mut producer, mut consumer) = rb.split();
+            for _ in 0..3 {
+                assert!(producer.push(Rc::clone(&counter)).is_ok());
+            }
+            drop(consumer.pop());
+            assert_eq!(Rc::strong_count(&counter), 3);
+        }
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[cfg_attr(miri, ignore)]
+    // this test takes far too long with Miri enabled
+    fn test_two_threads() {
+        const COUNT: u32 = 1_000_000;
+
+        let (mut producer, mut consumer) = SpscRingBuffer::new(100).split();
+
+        let t = thread::spawn(move || {
+            for i in 0..COUNT {
+                let mut value = i;
+                while let Err(v) = producer.push(value) {
+                    value = v;
+                    thread::yield_now();
+                }
+            }
+        });
+
+        let mut expected = 0;
+        while expected < COUNT {
+            match consumer.pop() {
+                Some(v) => {
+                    assert_eq!(v, expected);
+                    expected += 1;
+                }
+                None => thread::yield_now(),
+            }
+        }
+
+        t.join().unwrap();
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "Capacity must be greater than 0")]
+    fn test_zero_capacity() {
+        let _ = SpscRingBuffer::<i32>::new(0);
+    }
+
+    #[test]
+    fn test_try_new() {
+        assert!(SpscRingBuffer::<i32>::try_new(0).is_err());
+        assert_eq!(SpscRingBuffer::<i32>::try_new(3).unwrap().capacity(), 3);
+        assert_eq!(
+            SpscRingBuffer::<i32>::try_new(usize::MAX).err(),
+            Some(RingBufferError::CapacityOverflow)
+        );
+    }
+
+    #[test]
+    fn test_from_alloc_ringbuffer() {
+        let mut rb = AllocRingBuffer::new(3);
+        rb.extend([1, 2, 3, 4]);
+
+        let (mut producer, mut consumer) = rb.split();
+        assert_eq!(producer.capacity(), 3);
+        assert!(producer.is_full());
+        assert_eq!(producer.push(5), Err(5));
+
+        assert_eq!(consumer.pop(), Some(2));
+        assert_eq!(producer.push(5), Ok(()));
+        assert_eq!(consumer.pop(), Some(3));
+        assert_eq!(consumer.pop(), Some(4));
+        assert_eq!(consumer.pop(), Some(5));
+        assert_eq!(consumer.pop(), None);
+    }
+
+    #[test]
+    fn test_drop_halves_in_any_order() {
+        use std::rc::Rc;
+
+        let counter = Rc::new(());
+
+        let (mut producer, consumer) = SpscRingBuffer::new(2).split();
+        assert!(producer.push(Rc::clone(&counter)).is_ok());
+        drop(producer);
+        // the consumer still keeps the storage alive
+        assert_eq!(Rc::strong_count(&counter), 2);
+        drop(consumer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+
+        let (mut producer, consumer) = SpscRingBuffer::new(2).split();
+        assert!(producer.push(Rc::clone(&counter)).is_ok());
+        drop(consumer);
+        assert_eq!(Rc::strong_count(&counter), 2);
+        drop(producer);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+}
@@ -1,6 +1,10 @@
-use crate::ringbuffer_trait::{RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator};
+use crate::ringbuffer_trait::{
+    DebugContents, PushReport, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+};
 use crate::{AllocRingBuffer, RingBuffer};
 use alloc::collections::VecDeque;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
 use core::ops::{Deref, DerefMut, Index, IndexMut};
 
 /// A growable ringbuffer. Once capacity is reached, the size is doubled.
@@ -8,9 +12,28 @@ use core::ops::{Deref, DerefMut, Index, IndexMut};
 ///
 /// The reason this is a wrapper, is that we want `RingBuffers` to implement `Index<isize>`,
 /// which we cannot do for remote types like `VecDeque`
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct GrowableAllocRingBuffer<T>(VecDeque<T>);
 
+impl<T: Debug> Debug for GrowableAllocRingBuffer<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GrowableAllocRingBuffer")
+            .field("capacity", &self.0.capacity())
+            .field("len", &self.0.len())
+            .field("data", &DebugContents::new(self))
+            .finish()
+    }
+}
+
+impl_partial_eq_sequences!(GrowableAllocRingBuffer<T>,);
+
+impl<T: Hash> Hash for GrowableAllocRingBuffer<T> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.0.len());
+        self.0.iter().for_each(|i| i.hash(state));
+    }
+}
+
 impl<T, const N: usize> From<[T; N]> for GrowableAllocRingBuffer<T> {
     fn from(value: [T; N]) -> Self {
         Self(VecDeque::from(value))
@@ -135,10 +158,37 @@ impl<T> GrowableAllocRingBuffer<T> {
     }
 
     /// Creates an empty ringbuffer with space for at least capacity elements.
+    /// A capacity of zero is fine, the ringbuffer grows on the first push.
     #[must_use]
     pub fn with_capacity(capacity: usize) -> Self {
         Self(VecDeque::with_capacity(capacity))
     }
+
+    /// Moves the elements out of the ringbuffer into a vector, in dequeue order.
+    /// Unlike [`to_vec`](RingBuffer::to_vec), this doesn't clone the elements.
+    #[must_use]
+    pub fn into_vec(self) -> alloc::vec::Vec<T> {
+        self.0.into()
+    }
+
+    /// Splits the ringbuffer into two at the given index, see [`VecDeque::split_off`].
+    /// Afterwards `self` contains the elements `0..at`, and the returned ringbuffer contains
+    /// the elements `at..len`.
+    ///
+    /// # Panics
+    /// Panics if `at > len`
+    #[must_use = "use `truncate_front` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        Self(self.0.split_off(at))
+    }
+
+    /// Consumes the ringbuffer and applies `f` to every element in dequeue order,
+    /// returning a new ringbuffer holding the results.
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> GrowableAllocRingBuffer<U> {
+        let mut res = VecDeque::with_capacity(self.0.capacity());
+        res.extend(self.0.into_iter().map(f));
+        GrowableAllocRingBuffer(res)
+    }
 }
 
 impl<T> IntoIterator for GrowableAllocRingBuffer<T> {
@@ -190,6 +240,31 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
         self.push_back(value);
     }
 
+    fn try_push(&mut self, value: T) -> Result<(), T> {
+        // a growable ringbuffer is never full
+        self.0.push_back(value);
+        Ok(())
+    }
+
+    fn push_all_reporting<I: IntoIterator<Item = T>>(&mut self, iter: I) -> PushReport {
+        // a growable ringbuffer never rejects items
+        let len = self.0.len();
+        self.0.extend(iter);
+        PushReport::new(self.0.len() - len, 0, self.0.len(), self.0.capacity())
+    }
+
+    fn push_force_pop(&mut self, value: T) -> Option<T> {
+        // a growable ringbuffer never needs to evict anything
+        self.push_back(value);
+        None
+    }
+
+    fn saturating_push(&mut self, value: T) -> bool {
+        // a growable ringbuffer never needs to evict anything
+        self.push_back(value);
+        false
+    }
+
     fn fill_with<F: FnMut() -> T>(&mut self, mut f: F) {
         self.clear();
         let initial_capacity = self.0.capacity();
@@ -204,6 +279,65 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
         self.0.clear();
     }
 
+    fn is_contiguous(&self) -> bool {
+        self.0.as_slices().1.is_empty()
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        self.0.as_slices()
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        self.0.as_mut_slices()
+    }
+
+    fn insert(&mut self, index: usize, value: T) -> Result<(), T> {
+        // a growable ringbuffer is never full
+        self.0.insert(index, value);
+        Ok(())
+    }
+
+    fn remove(&mut self, index: usize) -> Option<T> {
+        self.0.remove(index)
+    }
+
+    fn swap_remove_front(&mut self, index: usize) -> Option<T> {
+        self.0.swap_remove_front(index)
+    }
+
+    fn truncate(&mut self, len: usize) {
+        let remove = self.0.len().saturating_sub(len);
+        drop(self.0.drain(..remove));
+    }
+
+    fn skip_n(&mut self, n: usize) -> usize {
+        let n = n.min(self.0.len());
+        let _ = self.0.drain(..n);
+        n
+    }
+
+    fn swap_remove_back(&mut self, index: usize) -> Option<T> {
+        self.0.swap_remove_back(index)
+    }
+
+    fn truncate_front(&mut self, len: usize) {
+        self.0.truncate(len);
+    }
+
+    fn rotate_left(&mut self, n: usize) {
+        if !self.is_empty() {
+            let len = self.0.len();
+            self.0.rotate_left(n % len);
+        }
+    }
+
+    fn rotate_right(&mut self, n: usize) {
+        if !self.is_empty() {
+            let len = self.0.len();
+            self.0.rotate_right(n % len);
+        }
+    }
+
     fn get(&self, index: usize) -> Option<&T> {
         if self.is_empty() {
             None
@@ -235,7 +369,7 @@ unsafe impl<T> RingBuffer<T> for GrowableAllocRingBuffer<T> {
         } else {
             let len = Self::ptr_len(rb);
 
-            let positive_index = -index as usize + 1;
+            let positive_index = -index as usize - 1;
             let masked = crate::mask_modulo(len, positive_index);
             let index = len - 1 - masked;
 
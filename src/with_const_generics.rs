@@ -1,9 +1,15 @@
-use crate::ringbuffer_trait::{RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator};
+use crate::ringbuffer_trait::{
+    DebugContents, RingBufferIntoIterator, RingBufferIterator, RingBufferMutIterator,
+};
 use crate::RingBuffer;
+use core::cmp::Ordering;
+use core::fmt::{self, Debug, Formatter};
+use core::hash::{Hash, Hasher};
 use core::iter::FromIterator;
 use core::mem;
 use core::mem::MaybeUninit;
 use core::ops::{Index, IndexMut};
+use core::ptr;
 
 /// The `ConstGenericRingBuffer` struct is a `RingBuffer` implementation which does not require `alloc` but
 /// uses const generics instead.
@@ -11,6 +17,16 @@ use core::ops::{Index, IndexMut};
 /// [`ConstGenericRingBuffer`] allocates the ringbuffer on the stack, and the size must be known at
 /// compile time through const-generics.
 ///
+/// A capacity of zero is rejected at compile time: [`new`](ConstGenericRingBuffer::new) and
+/// every other way to construct a `ConstGenericRingBuffer<T, 0>` fail to compile.
+///
+/// # Memory layout
+/// The elements are stored inline in a `[MaybeUninit<T>; CAP]` array, next to the read and write
+/// pointers and the counters returned by [`overwrite_count`](ConstGenericRingBuffer::overwrite_count)
+/// and [`total_pushed`](ConstGenericRingBuffer::total_pushed). Nothing is ever allocated on the
+/// heap, so this ringbuffer works in `#![no_std]` crates without the `alloc` feature. Where it
+/// lives is up to the user: on the stack, in a `static`, or inside another struct.
+///
 /// # Example
 /// ```
 /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
@@ -33,22 +49,32 @@ use core::ops::{Index, IndexMut};
 /// buffer.push(1);
 /// assert_eq!(buffer.to_vec(), vec![42, 1]);
 /// ```
-#[derive(Debug)]
 pub struct ConstGenericRingBuffer<T, const CAP: usize> {
     buf: [MaybeUninit<T>; CAP],
     readptr: usize,
     writeptr: usize,
+
+    // number of elements overwritten by push since the last reset
+    overwrite_count: u64,
+    // number of elements pushed since the buffer was created
+    total_pushed: u64,
 }
 
 impl<T, const CAP: usize> From<[T; CAP]> for ConstGenericRingBuffer<T, CAP> {
     fn from(value: [T; CAP]) -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::ERROR_CAPACITY_IS_NOT_ALLOWED_TO_BE_ZERO;
+
         Self {
             // Safety:
             // T has the same layout as MaybeUninit<T>
             // [T; N] has the same layout as [MaybeUninit<T>; N]
-            buf: unsafe { mem::transmute_copy(&value) },
+            // The elements are moved into the ringbuffer, so they must not be dropped here.
+            buf: unsafe { mem::transmute_copy(&mem::ManuallyDrop::new(value)) },
             readptr: 0,
             writeptr: CAP,
+            overwrite_count: 0,
+            total_pushed: CAP as u64,
         }
     }
 }
@@ -135,10 +161,33 @@ impl<T, const CAP: usize> Drop for ConstGenericRingBuffer<T, CAP> {
     }
 }
 
+impl<T: Debug, const CAP: usize> Debug for ConstGenericRingBuffer<T, CAP> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConstGenericRingBuffer")
+            .field("capacity", &CAP)
+            .field("len", &self.len())
+            .field("data", &DebugContents::new(self))
+            .finish()
+    }
+}
+
 impl<T: Clone, const CAP: usize> Clone for ConstGenericRingBuffer<T, CAP> {
     fn clone(&self) -> Self {
+        // clone the live elements into the same slots, so no reordering or masking
+        // beyond the index is needed, and nothing is allocated
         let mut new = ConstGenericRingBuffer::<T, CAP>::new();
-        self.iter().cloned().for_each(|i| new.push(i));
+        new.readptr = self.readptr;
+        new.writeptr = self.readptr;
+        for i in self.readptr..self.writeptr {
+            let index = crate::mask_modulo(CAP, i);
+            // SAFETY: the slots between readptr and writeptr are initialized
+            let value = unsafe { self.buf[index].assume_init_ref() }.clone();
+            new.buf[index] = MaybeUninit::new(value);
+            // bump writeptr per element, so a panicking clone only drops what was cloned so far
+            new.writeptr += 1;
+        }
+        new.overwrite_count = self.overwrite_count;
+        new.total_pushed = self.total_pushed;
         new
     }
 }
@@ -161,6 +210,28 @@ impl<T: PartialEq, const CAP: usize> PartialEq for ConstGenericRingBuffer<T, CAP
 
 impl<T: PartialEq, const CAP: usize> Eq for ConstGenericRingBuffer<T, CAP> {}
 
+impl_partial_eq_sequences!(ConstGenericRingBuffer<T, CAP>, const CAP: usize);
+
+/// Compares the elements lexicographically, from the front.
+impl<T: PartialOrd, const CAP: usize> PartialOrd for ConstGenericRingBuffer<T, CAP> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.iter().partial_cmp(other.iter())
+    }
+}
+
+impl<T: Ord, const CAP: usize> Ord for ConstGenericRingBuffer<T, CAP> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.iter().cmp(other.iter())
+    }
+}
+
+impl<T: Hash, const CAP: usize> Hash for ConstGenericRingBuffer<T, CAP> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        state.write_usize(self.len());
+        self.iter().for_each(|i| i.hash(state));
+    }
+}
+
 impl<T, const CAP: usize> ConstGenericRingBuffer<T, CAP> {
     const ERROR_CAPACITY_IS_NOT_ALLOWED_TO_BE_ZERO: () =
         assert!(CAP != 0, "Capacity is not allowed to be zero");
@@ -186,8 +257,308 @@ impl<T, const CAP: usize> ConstGenericRingBuffer<T, CAP> {
             buf: unsafe { MaybeUninit::uninit().assume_init() },
             writeptr: 0,
             readptr: 0,
+            overwrite_count: 0,
+            total_pushed: 0,
+        }
+    }
+
+    /// Creates a *full* ringbuffer, where every slot holds a clone of `value`. Unlike
+    /// [`new`](ConstGenericRingBuffer::new), which starts out empty,
+    /// [`is_full`](RingBuffer::is_full) is true right away and `len() == CAP`.
+    ///
+    /// The buffer is in the same state as after pushing `value` `CAP` times into an empty one:
+    /// the read pointer is at index 0, the write pointer at `CAP`, and the `CAP` clones are
+    /// dequeued before anything pushed later.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 3>::filled(0);
+    /// assert!(rb.is_full());
+    /// assert_eq!(rb.to_vec(), vec![0, 0, 0]);
+    ///
+    /// rb.push(1);
+    /// assert_eq!(rb.dequeue(), Some(0));
+    /// assert_eq!(rb.to_vec(), vec![0, 1]);
+    /// ```
+    #[must_use]
+    pub fn filled(value: T) -> Self
+    where
+        T: Clone,
+    {
+        let mut res = Self::new();
+        res.extend(core::iter::repeat(value).take(CAP));
+        res
+    }
+
+    /// Creates a ringbuffer from the items of `iter`, like [`FromIterator`], but reports whether
+    /// they fit. When `iter` yields more than `CAP` items, the oldest ones are overwritten like
+    /// with `collect`, and `Err` is returned with the filled ringbuffer and the number of items
+    /// which were dropped.
+    ///
+    /// # Errors
+    /// Returns `Err` with the ringbuffer and the number of dropped items if `iter` yields more
+    /// than `CAP` items.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let rb = ConstGenericRingBuffer::<_, 3>::try_from_iter(1..=3).unwrap();
+    /// assert_eq!(rb.to_vec(), vec![1, 2, 3]);
+    ///
+    /// let (rb, dropped) = ConstGenericRingBuffer::<_, 3>::try_from_iter(1..=5).unwrap_err();
+    /// assert_eq!(rb.to_vec(), vec![3, 4, 5]);
+    /// assert_eq!(dropped, 2);
+    /// ```
+    pub fn try_from_iter<I: IntoIterator<Item = T>>(iter: I) -> Result<Self, (Self, usize)> {
+        let res: Self = iter.into_iter().collect();
+        match res.overwrite_count {
+            0 => Ok(res),
+            dropped => Err((res, dropped as usize)),
         }
     }
+
+    /// Returns the number of elements which were overwritten by [`push`](RingBuffer::push)
+    /// because the buffer was full, since the buffer was created or
+    /// [`reset_overwrite_count`](ConstGenericRingBuffer::reset_overwrite_count) was last called.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 2>::new();
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// assert_eq!(rb.overwrite_count(), 3);
+    ///
+    /// rb.reset_overwrite_count();
+    /// assert_eq!(rb.overwrite_count(), 0);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn overwrite_count(&self) -> u64 {
+        self.overwrite_count
+    }
+
+    /// Resets the number returned by [`overwrite_count`](ConstGenericRingBuffer::overwrite_count)
+    /// to zero.
+    #[inline]
+    pub fn reset_overwrite_count(&mut self) {
+        self.overwrite_count = 0;
+    }
+
+    /// Returns the total number of elements pushed into the ringbuffer since it was created,
+    /// including the ones which were overwritten or dequeued since. Comparing it to an earlier
+    /// value tells how many elements were pushed in between. [`fill`](RingBuffer::fill) counts
+    /// as pushing every slot, while methods which only reorder or remove elements, like
+    /// [`rotate_left`](RingBuffer::rotate_left) or [`dedup`](RingBuffer::dedup), don't count.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 2>::new();
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// let baseline = rb.total_pushed();
+    /// assert_eq!(baseline, 5);
+    ///
+    /// rb.push(6);
+    /// let _ = rb.dequeue();
+    /// assert_eq!(rb.total_pushed() - baseline, 1);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn total_pushed(&self) -> u64 {
+        self.total_pushed
+    }
+
+    /// Returns a pointer to the start of the backing storage. The elements are stored in a
+    /// single contiguous `[T; CAP]` array, which this points to the first slot of. Not every slot
+    /// is initialized: the elements start at [`read_index`](ConstGenericRingBuffer::read_index)
+    /// and wrap around the end of the array.
+    ///
+    /// The pointer is valid as long as the ringbuffer isn't moved.
+    #[inline]
+    #[must_use]
+    pub fn as_ptr(&self) -> *const T {
+        self.buf.as_ptr().cast()
+    }
+
+    /// Returns a mutable pointer to the start of the backing storage, see
+    /// [`as_ptr`](ConstGenericRingBuffer::as_ptr). This makes it possible to let a DMA
+    /// peripheral write into the free slots, starting at
+    /// [`write_index`](ConstGenericRingBuffer::write_index), and then commit those writes with
+    /// [`advance_write`](ConstGenericRingBuffer::advance_write).
+    ///
+    /// The pointer is valid as long as the ringbuffer isn't moved.
+    #[inline]
+    #[must_use]
+    pub fn as_mut_ptr(&mut self) -> *mut T {
+        self.buf.as_mut_ptr().cast()
+    }
+
+    /// Returns the index in the backing array of the oldest element, which is dequeued next.
+    #[inline]
+    #[must_use]
+    pub fn read_index(&self) -> usize {
+        crate::mask_modulo(CAP, self.readptr)
+    }
+
+    /// Returns the index in the backing array of the slot the next element is pushed into.
+    #[inline]
+    #[must_use]
+    pub fn write_index(&self) -> usize {
+        crate::mask_modulo(CAP, self.writeptr)
+    }
+
+    /// Adds the `n` slots starting at [`write_index`](ConstGenericRingBuffer::write_index),
+    /// wrapping around the end of the backing array, to the elements of the ringbuffer, as if
+    /// they had been pushed.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than the number of free slots, `CAP - len`
+    ///
+    /// # Safety
+    /// The caller must have initialized those `n` slots with valid values of `T`, for example
+    /// by writing through [`as_mut_ptr`](ConstGenericRingBuffer::as_mut_ptr). The ringbuffer
+    /// takes ownership of them, and drops them like any other element.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<u8, 4>::new();
+    /// rb.extend([1, 2, 3]);
+    /// let _ = rb.dequeue();
+    ///
+    /// // write two bytes behind the newest element, wrapping around the end of the array
+    /// let start = rb.write_index();
+    /// let ptr = rb.as_mut_ptr();
+    /// for (offset, byte) in [4, 5].into_iter().enumerate() {
+    ///     unsafe { ptr.add((start + offset) % 4).write(byte) };
+    /// }
+    /// unsafe { rb.advance_write(2) };
+    ///
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4, 5]);
+    /// ```
+    pub unsafe fn advance_write(&mut self, n: usize) {
+        assert!(
+            n <= CAP - self.len(),
+            "not enough free slots to advance over"
+        );
+
+        self.writeptr += n;
+        self.total_pushed += n as u64;
+    }
+
+    /// Removes the `n` oldest elements from the ringbuffer without dropping them. This is the
+    /// counterpart of [`advance_write`](ConstGenericRingBuffer::advance_write) for reading the
+    /// elements through [`as_ptr`](ConstGenericRingBuffer::as_ptr): the caller takes ownership
+    /// of them, for example by moving them out with [`ptr::read`](core::ptr::read). Elements which
+    /// aren't read are leaked. Use [`skip_n`](RingBuffer::skip_n) to drop them instead.
+    ///
+    /// # Panics
+    /// Panics if `n` is larger than `len`
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<u8, 4>::from([1, 2, 3, 4]);
+    /// let first = unsafe { rb.as_ptr().add(rb.read_index()).read() };
+    /// rb.advance_read(1);
+    /// assert_eq!(first, 1);
+    /// assert_eq!(rb.to_vec(), vec![2, 3, 4]);
+    /// ```
+    pub fn advance_read(&mut self, n: usize) {
+        assert!(n <= self.len(), "not enough elements to advance over");
+
+        self.readptr += n;
+    }
+
+    /// Returns an iterator over the elements, oldest first, paired with their sequence number:
+    /// the number of elements pushed before them since the ringbuffer was created. The newest
+    /// element has number [`total_pushed`](ConstGenericRingBuffer::total_pushed) - 1.
+    ///
+    /// The number of the element at index `i` is `total_pushed - len + i`, so the numbers stay
+    /// accurate as long as elements are only pushed to the back and removed from the front.
+    /// Methods which remove elements from the middle, like [`remove`](RingBuffer::remove),
+    /// [`retain`](RingBuffer::retain), [`swap_remove_back`](RingBuffer::swap_remove_back),
+    /// [`swap_remove_front`](RingBuffer::swap_remove_front) and [`dedup`](RingBuffer::dedup),
+    /// renumber the survivors that are older than the removed elements.
+    /// [`clear`](RingBuffer::clear) doesn't reset them.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 2>::new();
+    /// rb.extend(['a', 'b', 'c']);
+    /// assert_eq!(rb.iter_enumerated().collect::<Vec<_>>(), [(1, &'b'), (2, &'c')]);
+    ///
+    /// rb.clear();
+    /// rb.push('d');
+    /// assert_eq!(rb.iter_enumerated().collect::<Vec<_>>(), [(3, &'d')]);
+    /// ```
+    pub fn iter_enumerated(&self) -> impl Iterator<Item = (u64, &T)> {
+        (self.total_pushed.saturating_sub(self.len() as u64)..).zip(self.iter())
+    }
+
+    /// Moves the elements out of the ringbuffer into a vector, in dequeue order.
+    /// Unlike [`to_vec`](RingBuffer::to_vec), this doesn't clone the elements.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 2>::new();
+    /// rb.extend([1, 2, 3]);
+    /// assert_eq!(rb.into_vec(), vec![2, 3]);
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[must_use]
+    pub fn into_vec(self) -> alloc::vec::Vec<T> {
+        self.into_iter().collect()
+    }
+
+    /// Splits the ringbuffer into two at the given index. Afterwards `self` contains the elements
+    /// `0..at`, and the returned ringbuffer contains the elements `at..len`, both in dequeue
+    /// order. The returned ringbuffer has the same capacity as `self`.
+    ///
+    /// # Panics
+    /// Panics if `at > len`
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 5>::new();
+    /// rb.extend([1, 2, 3, 4, 5]);
+    /// let tail = rb.split_off(2);
+    /// assert_eq!(rb.to_vec(), vec![1, 2]);
+    /// assert_eq!(tail.to_vec(), vec![3, 4, 5]);
+    /// ```
+    #[must_use = "use `truncate_front` if you don't need the other half"]
+    pub fn split_off(&mut self, at: usize) -> Self {
+        let len = self.len();
+        assert!(at <= len, "`at` out of bounds");
+
+        // move the elements `at..len` to the front, so they can be dequeued in order
+        self.rotate_left(at);
+        let mut res = Self::new();
+        res.extend((at..len).filter_map(|_| self.dequeue()));
+        res
+    }
+
+    /// Consumes the ringbuffer and applies `f` to every element in dequeue order,
+    /// returning a new ringbuffer with the same capacity holding the results.
+    ///
+    /// ```
+    /// use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+    ///
+    /// let mut rb = ConstGenericRingBuffer::<_, 3>::new();
+    /// rb.extend([1, 2, 3, 4]);
+    ///
+    /// let mapped: ConstGenericRingBuffer<f32, 3> = rb.map(|i| i as f32 / 2.0);
+    /// assert_eq!(mapped.to_vec(), vec![1.0, 1.5, 2.0]);
+    /// ```
+    pub fn map<U, F: FnMut(T) -> U>(self, f: F) -> ConstGenericRingBuffer<U, CAP> {
+        let mut res = ConstGenericRingBuffer::new();
+        res.extend(self.into_iter().map(f));
+        res
+    }
 }
 
 /// Get a reference from the buffer without checking it is initialized
@@ -279,10 +650,90 @@ unsafe impl<T, const CAP: usize> RingBuffer<T> for ConstGenericRingBuffer<T, CAP
                 drop(previous_value.assume_init());
             }
             self.readptr += 1;
+            self.overwrite_count += 1;
         }
         let index = crate::mask_modulo(CAP, self.writeptr);
         self.buf[index] = MaybeUninit::new(value);
         self.writeptr += 1;
+        self.total_pushed += 1;
+    }
+
+    fn extend_from_slice(&mut self, items: &[T])
+    where
+        T: Copy,
+    {
+        let overwritten = (self.len() + items.len()).saturating_sub(CAP);
+        self.total_pushed += items.len() as u64;
+        self.overwrite_count += overwritten as u64;
+
+        // all but the last `CAP` items would be overwritten anyway
+        let skipped = items.len().saturating_sub(CAP);
+        let items = &items[skipped..];
+
+        let start = crate::mask_modulo(CAP, self.writeptr);
+        let first_len = items.len().min(CAP - start);
+        let buf = self.buf.as_mut_ptr().cast::<T>();
+
+        // SAFETY: both blocks lie within the array of `CAP` elements. T is Copy, so the
+        // overwritten elements don't need to be dropped.
+        unsafe {
+            ptr::copy_nonoverlapping(items.as_ptr(), buf.add(start), first_len);
+            ptr::copy_nonoverlapping(items.as_ptr().add(first_len), buf, items.len() - first_len);
+        }
+
+        self.writeptr += items.len();
+        self.readptr += overwritten - skipped;
+    }
+
+    fn copy_to_slice(&self, out: &mut [T]) -> usize
+    where
+        T: Copy,
+    {
+        let count = out.len().min(self.len());
+
+        let start = crate::mask_modulo(CAP, self.readptr);
+        let first_len = count.min(CAP - start);
+        let buf = self.buf.as_ptr().cast::<T>();
+
+        // SAFETY: the first `count` elements from the readptr are initialized, the second
+        // block only exists when they wrap around to the start of the array.
+        unsafe {
+            ptr::copy_nonoverlapping(buf.add(start), out.as_mut_ptr(), first_len);
+            ptr::copy_nonoverlapping(buf, out.as_mut_ptr().add(first_len), count - first_len);
+        }
+
+        count
+    }
+
+    fn as_slices(&self) -> (&[T], &[T]) {
+        let start = crate::mask_modulo(CAP, self.readptr);
+        let first_len = self.len().min(CAP - start);
+        let buf = self.buf.as_ptr().cast::<T>();
+
+        // SAFETY: the first `len` elements from the readptr are initialized, the second
+        // slice only has elements when they wrap around to the start of the array.
+        unsafe {
+            (
+                core::slice::from_raw_parts(buf.add(start), first_len),
+                core::slice::from_raw_parts(buf, self.len() - first_len),
+            )
+        }
+    }
+
+    fn as_mut_slices(&mut self) -> (&mut [T], &mut [T]) {
+        let start = crate::mask_modulo(CAP, self.readptr);
+        let first_len = self.len().min(CAP - start);
+        let len = self.len();
+        let buf = self.buf.as_mut_ptr().cast::<T>();
+
+        // SAFETY: like in as_slices. The first slice starts at or after the end of the second
+        // one, so they don't overlap.
+        unsafe {
+            (
+                core::slice::from_raw_parts_mut(buf.add(start), first_len),
+                core::slice::from_raw_parts_mut(buf, len - first_len),
+            )
+        }
     }
 
     fn dequeue(&mut self) -> Option<T> {
@@ -305,6 +756,7 @@ unsafe impl<T, const CAP: usize> RingBuffer<T> for ConstGenericRingBuffer<T, CAP
         get_unchecked_mut,
         readptr,
         writeptr,
+        total_pushed,
         crate::mask_modulo
     );
 
@@ -314,6 +766,7 @@ unsafe impl<T, const CAP: usize> RingBuffer<T> for ConstGenericRingBuffer<T, CAP
         self.readptr = 0;
         self.writeptr = CAP;
         self.buf.fill_with(|| MaybeUninit::new(f()));
+        self.total_pushed += CAP as u64;
     }
 }
 
@@ -370,10 +823,10 @@ mod tests {
             assert!(rb.is_full());
 
             for i in 0..10 {
-                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue())
+                assert_eq!(Some(i + NUM_VALS - rb.capacity()), rb.dequeue());
             }
 
-            assert!(rb.is_empty())
+            assert!(rb.is_empty());
         }
     }
 
@@ -418,8 +871,301 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_overwrite_count() {
+        let mut rb = ConstGenericRingBuffer::<i32, 3>::new();
+        rb.push(1);
+        rb.push(2);
+        rb.push(3);
+        // filling empty slots doesn't overwrite anything
+        assert_eq!(rb.overwrite_count(), 0);
+
+        rb.push(4);
+        rb.push(5);
+        assert_eq!(rb.overwrite_count(), 2);
+
+        let _ = rb.dequeue();
+        rb.push(6);
+        assert_eq!(rb.overwrite_count(), 2);
+
+        rb.reset_overwrite_count();
+        assert_eq!(rb.overwrite_count(), 0);
+        rb.push(7);
+        assert_eq!(rb.overwrite_count(), 1);
+        assert_eq!(rb.to_vec(), [5, 6, 7]);
+    }
+
+    #[test]
+    fn test_total_pushed() {
+        let mut rb = ConstGenericRingBuffer::<i32, 3>::new();
+        assert_eq!(rb.total_pushed(), 0);
+
+        for i in 0..10 {
+            rb.push(i);
+        }
+        assert_eq!(rb.total_pushed(), 10);
+
+        let _ = rb.dequeue();
+        rb.clear();
+        assert_eq!(rb.total_pushed(), 10);
+
+        rb.push(10);
+        assert_eq!(rb.total_pushed(), 11);
+        assert_eq!(rb.clone().total_pushed(), 11);
+
+        assert_eq!(ConstGenericRingBuffer::from([1, 2, 3]).total_pushed(), 3);
+    }
+
+    #[test]
+    fn test_total_pushed_reorganizing() {
+        // not full, so rotating requeues the elements
+        let mut rb = ConstGenericRingBuffer::<i32, 8>::new();
+        rb.extend([1, 1, 2, 3]);
+        rb.rotate_left(1);
+        rb.rotate_right(2);
+        assert_eq!(rb.total_pushed(), 4);
+
+        rb.dedup();
+        rb.retain(|&i| i != 2);
+        rb.retain_newest(8, |_| true);
+        assert_eq!(rb.total_pushed(), 4);
+
+        // the inserted element is the only one pushed
+        assert_eq!(rb.insert(1, 4), Ok(()));
+        assert_eq!(rb.total_pushed(), 5);
+    }
+
+    #[test]
+    fn test_raw_writes() {
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        assert_eq!((rb.read_index(), rb.write_index()), (0, 0));
+        assert_eq!(rb.as_ptr(), rb.as_mut_ptr() as *const i32);
+
+        rb.extend([1, 2, 3]);
+        let _ = rb.dequeue();
+        let _ = rb.dequeue();
+        assert_eq!((rb.read_index(), rb.write_index()), (2, 3));
+
+        let start = rb.write_index();
+        let ptr = rb.as_mut_ptr();
+        for (offset, i) in [4, 5, 6].into_iter().enumerate() {
+            unsafe { ptr.add((start + offset) % 4).write(i) };
+        }
+        unsafe { rb.advance_write(3) };
+
+        assert!(rb.is_full());
+        assert_eq!(rb.to_vec(), [3, 4, 5, 6]);
+        assert_eq!(rb.total_pushed(), 6);
+        assert_eq!(unsafe { *rb.as_ptr().add(rb.read_index()) }, 3);
+
+        unsafe { rb.advance_write(0) };
+        assert_eq!(rb.len(), 4);
+    }
+
+    #[test]
+    fn test_clone_wrapped() {
+        use alloc::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut rb = ConstGenericRingBuffer::<_, 4>::new();
+        rb.extend((0..6).map(|i| (i, Rc::clone(&counter))));
+        let _ = rb.dequeue();
+
+        let clone = rb.clone();
+        assert_eq!(clone.read_index(), rb.read_index());
+        assert_eq!(clone.write_index(), rb.write_index());
+        assert_eq!(clone.overwrite_count(), 2);
+        assert_eq!(clone.total_pushed(), 6);
+        assert_eq!(
+            clone
+                .iter()
+                .map(|(i, _)| *i)
+                .collect::<alloc::vec::Vec<_>>(),
+            [3, 4, 5]
+        );
+        assert_eq!(Rc::strong_count(&counter), 7);
+
+        drop(clone);
+        assert_eq!(Rc::strong_count(&counter), 4);
+    }
+
+    #[test]
+    fn test_advance_read() {
+        use alloc::rc::Rc;
+
+        let counter = Rc::new(());
+        let mut rb = ConstGenericRingBuffer::<_, 4>::new();
+        rb.extend((0..6).map(|i| (i, Rc::clone(&counter))));
+
+        let (first, _) = unsafe { rb.as_ptr().add(rb.read_index()).read() };
+        rb.advance_read(1);
+        assert_eq!(first, 2);
+        assert_eq!(rb.len(), 3);
+        assert_eq!(Rc::strong_count(&counter), 4);
+
+        rb.advance_read(0);
+        let _ = rb.skip_n(3);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_advance_read_past_len() {
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::from([1, 2, 3, 4]);
+        rb.advance_read(5);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_advance_write_past_capacity() {
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        rb.push(1);
+        unsafe { rb.advance_write(4) };
+    }
+
+    #[test]
+    fn test_try_from_iter() {
+        let rb = ConstGenericRingBuffer::<i32, 3>::try_from_iter(0..0).unwrap();
+        assert!(rb.is_empty());
+
+        let rb = ConstGenericRingBuffer::<i32, 3>::try_from_iter(0..3).unwrap();
+        assert_eq!(rb.to_vec(), [0, 1, 2]);
+
+        let (rb, dropped) = ConstGenericRingBuffer::<i32, 3>::try_from_iter(0..10).unwrap_err();
+        assert_eq!(rb.to_vec(), [7, 8, 9]);
+        assert_eq!(dropped, 7);
+        assert_eq!(rb.total_pushed(), 10);
+    }
+
+    #[test]
+    fn test_filled() {
+        let mut rb = ConstGenericRingBuffer::<i32, 5>::filled(7);
+        assert!(rb.is_full());
+        assert_eq!(rb.len(), 5);
+        assert_eq!(rb, [7; 5]);
+        assert_eq!(rb.overwrite_count(), 0);
+
+        for i in 0..5 {
+            rb.push(i);
+            assert_eq!(rb.len(), 5);
+        }
+        assert_eq!(rb, [0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_iter_enumerated() {
+        let mut rb = ConstGenericRingBuffer::<i32, 3>::new();
+        assert_eq!(rb.iter_enumerated().next(), None);
+
+        rb.extend(0..10);
+        let _ = rb.dequeue();
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(8, &8), (9, &9)]
+        );
+
+        rb.clear();
+        rb.push(10);
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(10, &10)]
+        );
+    }
+
+    #[test]
+    fn test_iter_enumerated_after_fill() {
+        let mut rb = ConstGenericRingBuffer::<i32, 4>::new();
+        rb.fill(0);
+        assert_eq!(rb.total_pushed(), 4);
+        assert_eq!(
+            rb.iter_enumerated()
+                .map(|(n, _)| n)
+                .collect::<alloc::vec::Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+
+        rb.push(1);
+        rb.fill_default();
+        assert_eq!(rb.iter_enumerated().next(), Some((5, &0)));
+    }
+
+    #[test]
+    fn test_iter_enumerated_after_removing() {
+        let mut rb = ConstGenericRingBuffer::<_, 4>::from(['a', 'b', 'c', 'd']);
+        // the survivors older than the removed element are renumbered
+        assert_eq!(rb.remove(1), Some('b'));
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(1, &'a'), (2, &'c'), (3, &'d')]
+        );
+
+        assert_eq!(rb.swap_remove_back(0), Some('a'));
+        rb.push('e');
+        assert_eq!(
+            rb.iter_enumerated().collect::<alloc::vec::Vec<_>>(),
+            [(2, &'d'), (3, &'c'), (4, &'e')]
+        );
+    }
+
+    #[test]
+    fn test_drop_only_live_elements() {
+        use core::cell::Cell;
+
+        struct Counted<'a>(&'a Cell<usize>);
+
+        impl Drop for Counted<'_> {
+            fn drop(&mut self) {
+                self.0.set(self.0.get() + 1);
+            }
+        }
+
+        // pushes `pushed` elements, dequeues `dequeued` of them and drops the buffer,
+        // returning the number of drops which happened when the buffer itself was dropped
+        fn drops_on_drop(pushed: usize, dequeued: usize) -> usize {
+            let drops = Cell::new(0);
+            let mut rb = ConstGenericRingBuffer::<_, 4>::new();
+            for _ in 0..pushed {
+                rb.push(Counted(&drops));
+            }
+            for _ in 0..dequeued {
+                drop(rb.dequeue());
+            }
+
+            let before = drops.get();
+            assert_eq!(before, pushed.saturating_sub(4) + dequeued);
+            drop(rb);
+            drops.get() - before
+        }
+
+        // empty
+        assert_eq!(drops_on_drop(0, 0), 0);
+        assert_eq!(drops_on_drop(2, 2), 0);
+        // partially full
+        assert_eq!(drops_on_drop(3, 0), 3);
+        assert_eq!(drops_on_drop(3, 2), 1);
+        // full
+        assert_eq!(drops_on_drop(4, 0), 4);
+        // wrapped
+        assert_eq!(drops_on_drop(6, 0), 4);
+        assert_eq!(drops_on_drop(6, 3), 1);
+    }
+
+    #[test]
+    fn test_stored_inline() {
+        use core::mem::size_of;
+
+        // the elements are part of the struct itself, there's no pointer to a separate allocation
+        assert!(size_of::<ConstGenericRingBuffer<u8, 256>>() >= 256);
+        assert!(size_of::<ConstGenericRingBuffer<u64, 64>>() >= 64 * size_of::<u64>());
+        assert_eq!(
+            size_of::<ConstGenericRingBuffer<u8, 256>>()
+                - size_of::<ConstGenericRingBuffer<u8, 0>>(),
+            256
+        );
+    }
+
     #[cfg(test)]
-    mod tests {
+    mod conversions {
         use crate::{AllocRingBuffer, ConstGenericRingBuffer, GrowableAllocRingBuffer, RingBuffer};
         use alloc::collections::{LinkedList, VecDeque};
         use alloc::string::ToString;
@@ -492,5 +1238,32 @@ mod tests {
                 vec![1, 2, 3]
             );
         }
+
+        #[test]
+        fn from_moves_elements() {
+            use alloc::rc::Rc;
+
+            let counter = Rc::new(());
+            let rb = ConstGenericRingBuffer::from([Rc::clone(&counter), Rc::clone(&counter)]);
+            assert_eq!(Rc::strong_count(&counter), 3);
+            drop(rb);
+            assert_eq!(Rc::strong_count(&counter), 1);
+
+            let rb = AllocRingBuffer::from(vec![Rc::clone(&counter), Rc::clone(&counter)]);
+            assert_eq!(Rc::strong_count(&counter), 3);
+            assert_eq!(rb.capacity(), 2);
+            drop(rb);
+            assert_eq!(Rc::strong_count(&counter), 1);
+        }
+
+        #[test]
+        fn into() {
+            let b: AllocRingBuffer<_> = vec![1, 2, 3].into();
+            assert_eq!(b.to_vec(), vec![1, 2, 3]);
+
+            let b: ConstGenericRingBuffer<_, 3> = [1, 2, 3].into();
+            assert_eq!(b.to_vec(), vec![1, 2, 3]);
+            assert!(b.is_full());
+        }
     }
 }
@@ -0,0 +1,38 @@
+//! Checks that cloning a `ConstGenericRingBuffer` never touches the heap. This lives in its own
+//! test binary, since it needs a counting global allocator.
+
+use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.fetch_add(1, Ordering::SeqCst);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+#[test]
+fn test_const_clone_does_not_allocate() {
+    let mut rb = ConstGenericRingBuffer::<u64, 1024>::new();
+    rb.extend(0..1500);
+
+    let before = ALLOCATIONS.load(Ordering::SeqCst);
+    let clone = ConstGenericRingBuffer::clone(&rb);
+    let after = ALLOCATIONS.load(Ordering::SeqCst);
+
+    assert_eq!(before, after);
+    assert!(clone.iter().eq(rb.iter()));
+    assert_eq!(clone.peek(), Some(&476));
+}
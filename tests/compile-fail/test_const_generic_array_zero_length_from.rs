@@ -0,0 +1,10 @@
+extern crate ringbuffer;
+
+use ringbuffer::ConstGenericRingBuffer;
+
+fn main() {
+    let empty: [i32; 0] = [];
+    let _ = ConstGenericRingBuffer::from(empty);
+    //~^ note: ConstGenericRingBuffer
+    // ringbuffer can't be zero length
+}
@@ -0,0 +1,14 @@
+extern crate ringbuffer;
+
+use ringbuffer::{AllocRingBuffer, RingBuffer};
+
+fn main() {
+    let mut buf = AllocRingBuffer::new(4);
+    buf.extend([1, 2, 3]);
+
+    let mut iter = buf.iter();
+    buf.push(4);
+    //~^ ERROR cannot borrow `buf` as mutable because it is also borrowed as immutable
+    // the iterator borrows the ringbuffer until it is dropped
+    let _ = iter.next();
+}
@@ -0,0 +1,14 @@
+extern crate ringbuffer;
+
+use ringbuffer::{ConstGenericRingBuffer, RingBuffer};
+
+fn main() {
+    let mut buf = ConstGenericRingBuffer::<i32, 4>::new();
+    buf.extend([1, 2, 3]);
+
+    for i in buf.iter_mut() {
+        buf.push(*i);
+        //~^ ERROR cannot borrow `buf` as mutable more than once at a time
+        // the iterator borrows the ringbuffer mutably until it is dropped
+    }
+}